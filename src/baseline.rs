@@ -0,0 +1,149 @@
+//! The `baseline` module records the current findings, so that only new findings are reported.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use annotate_snippets::{Level, Renderer};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::finding::{Finding, Fingerprint};
+
+/// Id of the diagnostics for baseline entries which no longer match.
+pub const STALE_BASELINE_ID: &str = "STALE_BASELINE";
+
+/// Findings recorded in a baseline file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub findings: Vec<Fingerprint>,
+}
+
+impl From<&[Finding]> for Baseline {
+    fn from(findings: &[Finding]) -> Self {
+        let mut fingerprints: Vec<_> = findings.iter().map(Finding::fingerprint).collect();
+        fingerprints.sort();
+        Self {
+            findings: fingerprints,
+        }
+    }
+}
+
+impl Baseline {
+    /// Reads a baseline file.
+    ///
+    /// # Errors
+    /// If the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Writes a baseline file.
+    ///
+    /// # Errors
+    /// If the file cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Returns the findings which are not in the baseline, and the baseline
+    /// entries which no longer match any finding.
+    #[must_use]
+    pub fn filter(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<Fingerprint>) {
+        let mut counts: HashMap<&Fingerprint, usize> = HashMap::new();
+        for fingerprint in &self.findings {
+            *counts.entry(fingerprint).or_default() += 1;
+        }
+
+        let new_findings = findings
+            .into_iter()
+            .filter(|finding| match counts.get_mut(&finding.fingerprint()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+
+        let mut stale_entries = vec![];
+        for fingerprint in &self.findings {
+            if let Some(count) = counts.get_mut(fingerprint) {
+                if *count > 0 {
+                    *count -= 1;
+                    stale_entries.push(fingerprint.clone());
+                }
+            }
+        }
+
+        (new_findings, stale_entries)
+    }
+}
+
+/// Describes a baseline entry which no longer matches any finding.
+#[must_use]
+pub fn stale_entry_message(fingerprint: &Fingerprint) -> String {
+    format!(
+        "baseline entry no longer matches: {} `{}` {} {:?}",
+        fingerprint.file, fingerprint.item_path, fingerprint.rule_id, fingerprint.matched_text
+    )
+}
+
+/// Renders a baseline entry which no longer matches any finding.
+#[must_use]
+pub fn annotate_stale_entry(fingerprint: &Fingerprint, renderer: &Renderer) -> String {
+    let title = stale_entry_message(fingerprint);
+
+    let message = Level::Warning.title(&title).id(STALE_BASELINE_ID);
+
+    let annotation = renderer.render(message).to_string();
+    annotation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    #[test]
+    fn filter_new_and_stale() {
+        let old = finding("src/lib.rs", "RULE", "TYPOS", "teh");
+        let fixed = finding("src/lib.rs", "RULE", "TYPOS", "recieve");
+        let baseline = Baseline::from([old.clone(), fixed.clone()].as_slice());
+
+        let new = finding("src/lib.rs", "RULE", "TYPOS", "wierd");
+        let (new_findings, stale_entries) = baseline.filter(vec![old, new]);
+
+        assert_eq!(new_findings.len(), 1);
+        assert_eq!(new_findings[0].matched_text, "wierd");
+        assert_eq!(stale_entries, vec![fixed.fingerprint()]);
+    }
+
+    #[test]
+    fn filter_counts_duplicates() {
+        let old = finding("src/lib.rs", "RULE", "TYPOS", "teh");
+        let baseline = Baseline::from(core::slice::from_ref(&old));
+
+        let (new_findings, stale_entries) = baseline.filter(vec![old.clone(), old]);
+
+        assert_eq!(new_findings.len(), 1);
+        assert!(stale_entries.is_empty());
+    }
+
+    #[test]
+    fn filter_matches_moved_findings() {
+        let old = finding("./src/lib.rs", "RULE", "TYPOS", "teh");
+        let baseline = Baseline::from(core::slice::from_ref(&old));
+
+        let mut moved = finding("src/lib.rs", "RULE", "TYPOS", "teh");
+        moved.start.line = 42;
+        moved.byte_range = 1000..1003;
+
+        let (new_findings, stale_entries) = baseline.filter(vec![moved]);
+
+        assert!(new_findings.is_empty());
+        assert!(stale_entries.is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+use std::path::Path;
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use languagetool_rust::{CheckRequest, CheckResponse};
+use spellbook::Dictionary;
+
+use crate::checker::{Checker, Request};
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::{local_response, LocalMatch};
+use crate::markdown::prose_tokens;
+
+/// Rule id of the misspellings found by a Hunspell dictionary.
+pub const HUNSPELL_RULE_ID: &str = "HUNSPELL_RULE";
+
+/// Offline spellchecker using Hunspell dictionaries.
+pub struct HunspellChecker {
+    dictionary: Dictionary,
+    max_suggestions: usize,
+}
+
+impl HunspellChecker {
+    /// Loads the `.aff` and `.dic` files of a dictionary.
+    ///
+    /// The path may be given with or without the file extension, e.g.
+    /// `/usr/share/hunspell/en_US` or `/usr/share/hunspell/en_US.dic`.
+    ///
+    /// # Errors
+    /// If the dictionary files cannot be read or parsed.
+    pub fn new(path: impl AsRef<Path>, max_suggestions: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let aff = std::fs::read_to_string(path.with_extension("aff"))?;
+        let dic = std::fs::read_to_string(path.with_extension("dic"))?;
+
+        let dictionary = Dictionary::new(&aff, &dic).map_err(|error| {
+            eyre!(
+                "failed to parse hunspell dictionary {}: {error}",
+                path.display()
+            )
+        })?;
+
+        Ok(Self {
+            dictionary,
+            max_suggestions,
+        })
+    }
+
+    /// Returns true if the request allows reporting misspellings.
+    fn is_enabled(request: &CheckRequest) -> bool {
+        let category = Categories::Typos.to_string();
+        let contains = |list: &Option<Vec<String>>, value: &str| {
+            list.as_ref()
+                .is_some_and(|list| list.iter().any(|each| each == value))
+        };
+
+        if contains(&request.disabled_rules, HUNSPELL_RULE_ID)
+            || contains(&request.disabled_categories, &category)
+        {
+            return false;
+        }
+
+        !request.enabled_only
+            || contains(&request.enabled_rules, HUNSPELL_RULE_ID)
+            || contains(&request.enabled_categories, &category)
+    }
+}
+
+impl Checker for HunspellChecker {
+    fn check(&self, request: &Request) -> Result<CheckResponse> {
+        let request = &request.check_request;
+        let text = request.text.as_deref().unwrap_or_default();
+
+        let mut matches = vec![];
+
+        if Self::is_enabled(request) {
+            for (offset, word) in prose_words(text) {
+                if self.dictionary.check(word) {
+                    continue;
+                }
+
+                let mut replacements = vec![];
+                self.dictionary.suggest(word, &mut replacements);
+                replacements.truncate(self.max_suggestions);
+
+                matches.push(
+                    LocalMatch {
+                        offset,
+                        length: word.chars().count(),
+                        message: "Possible spelling mistake found.".to_owned(),
+                        short_message: "Spelling mistake".to_owned(),
+                        replacements,
+                        rule_id: HUNSPELL_RULE_ID.to_owned(),
+                        rule_description: "Possible spelling mistake".to_owned(),
+                        issue_type: "misspelling".to_owned(),
+                        category_id: Categories::Typos.to_string(),
+                        category_name: "Possible Typo".to_owned(),
+                    }
+                    .into_match(text)?,
+                );
+            }
+        }
+
+        local_response(&request.language, &matches)
+    }
+
+    fn is_cacheable(&self) -> bool {
+        // local lookups are cheap, and the results depend on the dictionary.
+        false
+    }
+}
+
+/// Splits the prose of a markdown text into words.
+///
+/// Code blocks, inline code and tokens with digits or symbols (paths, URLs,
+/// identifiers) are skipped. Returns the char offset of each word.
+fn prose_words(text: &str) -> Vec<(usize, &str)> {
+    prose_tokens(text, "-/()[]{}<>\"*,;!?")
+        .into_iter()
+        .filter_map(|(offset, token)| {
+            let word = token.trim_start_matches(['.', ':', '\'']);
+            let skipped = token.chars().count() - word.chars().count();
+            let word = word.trim_end_matches(['.', ':', '\'']);
+
+            (!word.is_empty() && word.chars().all(|chr| chr.is_alphabetic() || chr == '\''))
+                .then_some((offset + skipped, word))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Writes a small dictionary in a new directory, and returns its path without extension.
+    fn dictionary(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("languagetool-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("en_US.aff"),
+            "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("en_US.dic"), "4\nthe\nvalue\nwidget\nüber\n").unwrap();
+        dir.join("en_US")
+    }
+
+    fn request(text: &str) -> Request {
+        CheckRequest::default()
+            .with_text(text.to_owned())
+            .with_language("en-US".to_owned())
+            .into()
+    }
+
+    #[test]
+    fn new() {
+        let path = dictionary("hunspell-new");
+
+        assert!(HunspellChecker::new(&path, 5).is_ok());
+        assert!(HunspellChecker::new(path.with_extension("dic"), 5).is_ok());
+        assert!(HunspellChecker::new(path.with_file_name("de_DE"), 5).is_err());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn check() {
+        let path = dictionary("hunspell-check");
+        let checker = HunspellChecker::new(&path, 1).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let response = checker
+            .check(&request("Über `teh` widget: teh value.\n\n```\nteh\n```\n"))
+            .unwrap();
+
+        assert_eq!(response.matches.len(), 1);
+        let typo = &response.matches[0];
+        assert_eq!((typo.offset, typo.length), (19, 3));
+        assert_eq!(typo.rule.id, HUNSPELL_RULE_ID);
+        assert_eq!(
+            typo.replacements
+                .iter()
+                .map(|replacement| replacement.value.as_str())
+                .collect::<Vec<_>>(),
+            ["the"]
+        );
+    }
+
+    #[test]
+    fn is_enabled() {
+        let enabled = |change: fn(&mut CheckRequest)| {
+            let mut request = CheckRequest::default();
+            change(&mut request);
+            HunspellChecker::is_enabled(&request)
+        };
+
+        assert!(enabled(|_| {}));
+        assert!(!enabled(|request| {
+            request.disabled_rules = Some(vec![HUNSPELL_RULE_ID.to_owned()]);
+        }));
+        assert!(!enabled(|request| {
+            request.disabled_categories = Some(vec!["TYPOS".to_owned()]);
+        }));
+        assert!(!enabled(|request| {
+            request.enabled_only = true;
+            request.enabled_rules = Some(vec!["EN_A_VS_AN".to_owned()]);
+        }));
+        assert!(enabled(|request| {
+            request.enabled_only = true;
+            request.enabled_rules = Some(vec![HUNSPELL_RULE_ID.to_owned()]);
+        }));
+        assert!(enabled(|request| {
+            request.enabled_only = true;
+            request.enabled_categories = Some(vec!["TYPOS".to_owned()]);
+        }));
+    }
+
+    #[test]
+    fn prose_words() {
+        let text = "Über, `code` the 'widget'... x2 foo.bar path/to (value)\n\n```\nteh\n```\n";
+
+        assert_eq!(
+            super::prose_words(text),
+            vec![
+                (0, "Über"),
+                (13, "the"),
+                (18, "widget"),
+                (40, "path"),
+                (45, "to"),
+                (49, "value"),
+            ]
+        );
+    }
+}
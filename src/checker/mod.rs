@@ -0,0 +1,71 @@
+pub mod hunspell;
+pub mod server;
+
+use color_eyre::Result;
+use languagetool_rust::{CheckRequest, CheckResponse};
+use serde::Serialize;
+
+/// A check request, with the options which `CheckRequest` does not have.
+///
+/// It serializes as the `CheckRequest` when these options are not set, so
+/// that the cached responses are still found.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(flatten)]
+    pub check_request: CheckRequest,
+    /// Languages whose words are accepted, comma-separated, e.g. `de-DE,fr`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_languages: Option<String>,
+}
+
+impl From<CheckRequest> for Request {
+    fn from(check_request: CheckRequest) -> Self {
+        Self {
+            check_request,
+            alt_languages: None,
+        }
+    }
+}
+
+pub trait Checker {
+    /// Check the text of a request.
+    ///
+    /// # Errors
+    /// If the text cannot be checked.
+    fn check(&self, request: &Request) -> Result<CheckResponse>;
+
+    /// Whether the responses should be stored in the cache database.
+    #[must_use]
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_without_alt_languages() {
+        let check_request = CheckRequest::default().with_text("Teh text.".to_owned());
+
+        assert_eq!(
+            serde_json::to_value(Request::from(check_request.clone())).unwrap(),
+            serde_json::to_value(check_request).unwrap()
+        );
+    }
+
+    #[test]
+    fn request_with_alt_languages() {
+        let request = Request {
+            check_request: CheckRequest::default().with_text("Teh text.".to_owned()),
+            alt_languages: Some("de-DE,fr".to_owned()),
+        };
+
+        let value = serde_json::to_value(request).unwrap();
+
+        assert_eq!(value["altLanguages"], "de-DE,fr");
+        assert_eq!(value["text"], "Teh text.");
+    }
+}
@@ -0,0 +1,180 @@
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use languagetool_rust::{CheckResponse, ServerClient};
+
+use crate::checker::{Checker, Request};
+
+/// A `languagetool` server.
+///
+/// The requests are posted directly with the HTTP client of `languagetool-rust`,
+/// rather than with its `check`, so that the options of `Request` it does not
+/// know, e.g. `altLanguages`, are sent.
+pub struct Server {
+    client: ServerClient,
+    max_suggestions: usize,
+}
+
+impl Server {
+    /// Checks with the server at the hostname and the port, which may be empty.
+    #[must_use]
+    pub fn new(hostname: &str, port: &str, max_suggestions: usize) -> Self {
+        Self {
+            client: ServerClient::new(hostname, port),
+            max_suggestions,
+        }
+    }
+}
+
+impl Checker for Server {
+    fn check(&self, request: &Request) -> Result<CheckResponse> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .enable_io()
+            .build()?;
+
+        // the errors of the client are reported without their URL, whose query
+        // holds the username and the API key.
+        let body = rt.block_on(async {
+            let response = self
+                .client
+                .client
+                .post(format!("{}/check", self.client.api))
+                .query(request)
+                .send()
+                .await
+                .map_err(|error| eyre!(error.without_url()))?;
+
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .map_err(|error| eyre!(error.without_url()))?;
+
+            if status.is_success() {
+                Ok(text)
+            } else {
+                // e.g. "Invalid language code".
+                Err(eyre!("{status}: {text}"))
+            }
+        })?;
+
+        let mut response: CheckResponse = serde_json::from_str(&body)?;
+
+        for each_match in &mut response.matches {
+            each_match.replacements.truncate(self.max_suggestions);
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use languagetool_rust::CheckRequest;
+
+    use super::*;
+    use crate::languagetool::local::{local_response, LocalMatch};
+
+    /// Answers one request with a status and a body, and returns its request line.
+    fn serve(status: &'static str, body: String) -> (u16, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            String::from_utf8_lossy(&request)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_owned()
+        });
+
+        (port, handle)
+    }
+
+    fn request() -> Request {
+        let mut check_request = CheckRequest::default().with_text("Teh text.".to_owned());
+        check_request.username = Some("user".to_owned());
+        check_request.api_key = Some("secret".to_owned());
+
+        Request {
+            check_request,
+            alt_languages: Some("de-DE".to_owned()),
+        }
+    }
+
+    #[test]
+    fn check() {
+        let text = "Teh text.";
+        let typo = LocalMatch {
+            offset: 0,
+            length: 3,
+            message: String::new(),
+            short_message: String::new(),
+            replacements: vec!["The".to_owned(), "Tea".to_owned(), "Ten".to_owned()],
+            rule_id: "MORFOLOGIK_RULE_EN_US".to_owned(),
+            rule_description: String::new(),
+            issue_type: "misspelling".to_owned(),
+            category_id: "TYPOS".to_owned(),
+            category_name: String::new(),
+        }
+        .into_match(text)
+        .unwrap();
+        let body = serde_json::to_string(&local_response("en-US", &[typo]).unwrap()).unwrap();
+        let (port, handle) = serve("200 OK", body);
+
+        let response = Server::new("http://127.0.0.1", &port.to_string(), 2)
+            .check(&request())
+            .unwrap();
+
+        let request_line = handle.join().unwrap();
+        assert!(request_line.starts_with("POST /v2/check?"));
+        assert!(request_line.contains("altLanguages=de-DE"));
+        assert_eq!(response.matches[0].replacements.len(), 2);
+    }
+
+    #[test]
+    fn errors_without_the_api_key() {
+        let (port, handle) = serve("400 Bad Request", "Invalid language code".to_owned());
+
+        let error = Server::new("http://127.0.0.1", &port.to_string(), 5)
+            .check(&request())
+            .unwrap_err()
+            .to_string();
+        handle.join().unwrap();
+
+        assert_eq!(error, "400 Bad Request: Invalid language code");
+    }
+
+    #[test]
+    fn connection_errors_without_the_api_key() {
+        // a port without a server.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let error = Server::new("http://127.0.0.1", &port.to_string(), 5)
+            .check(&request())
+            .unwrap_err();
+
+        assert!(!format!("{error:?}").contains("secret"));
+    }
+}
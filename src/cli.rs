@@ -1,19 +1,47 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 use clap::{Args, Parser};
-use color_eyre::eyre::ContextCompat;
+use color_eyre::eyre::{ContextCompat, WrapErr};
 use color_eyre::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 
+use crate::baseline::Baseline;
 use crate::cache::sled::SledCacheStore;
 use crate::cache::Cacheable;
+use crate::checker::hunspell::HunspellChecker;
+use crate::checker::server::Server;
+use crate::checker::Checker;
+use crate::config::{ConfigFile, LanguageMismatch, LanguageOverride, CONFIG_FILE};
+use crate::dictionary::DICTIONARY_FILE;
+use crate::finding::{Finding, Severity};
+use crate::fix::{unified_diff, Fixes, DEFAULT_FIX_CATEGORIES};
+use crate::gate::failures;
+use crate::interactive;
 use crate::languagetool::categories::Categories;
-use crate::utils::{check_and_annotate, fetch_docs};
+use crate::languagetool::local_server::LocalServer;
+use crate::report::summary::Summary;
+use crate::report::{Format, GroupBy, MessageFormat, Report, SortBy};
+use crate::rules::conventions::DEFAULT_MAX_SUMMARY_LENGTH;
+use crate::rules::glossary::GLOSSARY_FILE;
+use crate::utils::{check_files, fetch_docs, relative_path, CheckResults};
+
+/// Exit code of a run which failed because of the findings.
+pub const FINDINGS_EXIT_CODE: u8 = 1;
+/// Exit code of a run which failed because of an error.
+pub const ERROR_EXIT_CODE: u8 = 2;
+
+const DEFAULT_LANGUAGE: &str = "en-US";
+const DEFAULT_MAX_SUGGESTIONS: usize = 5;
 
 #[allow(
     clippy::struct_excessive_bools,
     reason = "This is a CLI configuration struct."
 )]
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 pub struct Config {
     #[clap(
         long,
@@ -42,12 +70,152 @@ pub struct Config {
     #[clap(long)]
     pub enable_only: bool,
 
-    #[clap(long, default_value = "en-US")]
-    pub language: String,
+    #[clap(
+        long,
+        help = "Language of the docs. [default: en-US, or auto with --preferred-variants]"
+    )]
+    pub language: Option<String>,
+    #[clap(long, help = "Native language of the author, to detect false friends.")]
+    pub mother_tongue: Option<String>,
+    #[clap(
+        long,
+        help = "Language variants to use with auto-detected languages, e.g. en-GB or de-AT."
+    )]
+    pub preferred_variants: Vec<String>,
+    #[clap(
+        long,
+        help = "Languages whose words are not reported as misspellings, e.g. de-DE or fr."
+    )]
+    pub alt_languages: Vec<String>,
+
+    #[clap(long, help = "Dictionaries to use, for premium users.")]
+    pub dicts: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Maximum number of suggestions per mistake, 0 for all of them. [default: 5]"
+    )]
+    pub max_suggestions: Option<usize>,
+
+    #[clap(skip)]
+    pub language_overrides: Vec<LanguageOverride>,
+    /// The path globs of `language_overrides`, in the same order.
+    #[clap(skip)]
+    pub language_globs: GlobSet,
+    #[clap(
+        long,
+        value_enum,
+        help = "Report docs whose detected language differs from the configured one. [default: warn]"
+    )]
+    pub language_mismatch: Option<LanguageMismatch>,
 
     #[clap(long)]
     pub picky: bool,
 
+    #[clap(
+        long,
+        env = "LANGUAGETOOL_HUNSPELL",
+        help = "Check spelling offline with a Hunspell dictionary (path to its `.aff` and `.dic` files).",
+        conflicts_with = "local_server"
+    )]
+    pub hunspell: Option<PathBuf>,
+
+    #[clap(
+        long,
+        env = "LANGUAGETOOL_LOCAL_SERVER",
+        help = "Start a local server from `languagetool-server.jar` for the check."
+    )]
+    pub local_server: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Keep the local server running and reuse it across invocations."
+    )]
+    pub reuse_local_server: bool,
+
+    #[clap(
+        long,
+        help = "Report only the findings which are not recorded in the baseline file."
+    )]
+    pub baseline: Option<PathBuf>,
+    #[clap(long, help = "Record the current findings in a baseline file.")]
+    pub write_baseline: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Fail on the findings of a rule, a category or a severity (error, warning or note)."
+    )]
+    pub deny: Vec<String>,
+    #[clap(long, help = "Fail if there are more findings.")]
+    pub max_findings: Option<usize>,
+    #[clap(skip)]
+    pub max_category_findings: BTreeMap<String, usize>,
+
+    #[clap(skip)]
+    pub severities: BTreeMap<String, Severity>,
+
+    #[clap(long, value_enum, help = "Output format. [default: human]")]
+    pub format: Option<Format>,
+    #[clap(
+        long,
+        value_enum,
+        conflicts_with = "format",
+        help = "Output format, as in `cargo check`. `json` is the same as `--format rustc`."
+    )]
+    pub message_format: Option<MessageFormat>,
+    #[clap(long, help = "Write the output to a file instead of stdout.")]
+    pub output: Option<PathBuf>,
+    #[clap(long, value_enum, help = "Group the findings in the human output.")]
+    pub group_by: Option<GroupBy>,
+    #[clap(long, value_enum, help = "Order of the findings. [default: location]")]
+    pub sort: Option<SortBy>,
+
+    #[clap(
+        long,
+        help = "Apply the first replacement of the findings of the fix categories."
+    )]
+    pub fix: bool,
+    #[clap(
+        long,
+        help = "Categories whose findings are fixed. [default: TYPOS, PUNCTUATION, CASING]"
+    )]
+    pub fix_categories: Vec<String>,
+    #[clap(
+        long,
+        requires = "fix",
+        help = "Print the fixes as a unified diff instead of writing the files."
+    )]
+    pub diff: bool,
+    #[clap(
+        long,
+        requires = "fix",
+        help = "Write the fixes as a unified diff to a file instead of writing the files."
+    )]
+    pub patch_file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        conflicts_with = "fix",
+        help = "Walk through the findings and choose how to fix each of them."
+    )]
+    pub interactive: bool,
+    #[clap(
+        long,
+        help = "Words which are not misspellings, one per line. [default: languagetool-dictionary.txt]"
+    )]
+    pub dictionary: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Preferred terms and their banned variants. [default: languagetool-glossary.toml]"
+    )]
+    pub glossary: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Length limit of the summaries of the docs, in chars, for DOC_SUMMARY_LENGTH. [default: 120]"
+    )]
+    pub max_summary_length: Option<usize>,
+    #[clap(long, help = "Check the conventions of rustdoc, i.e. the DOCS rules.")]
+    pub doc_conventions: bool,
+
     #[clap(long, help = "Disable cache query.")]
     pub no_cache: bool,
 
@@ -55,12 +223,243 @@ pub struct Config {
     pub show_all: bool,
 }
 
+impl Config {
+    /// The language of the docs.
+    ///
+    /// Preferred variants are only used by `languagetool` when the language is
+    /// auto-detected, so this defaults to `auto` when they are set.
+    #[must_use]
+    pub fn language(&self) -> &str {
+        self.language
+            .as_deref()
+            .unwrap_or(if self.preferred_variants.is_empty() {
+                DEFAULT_LANGUAGE
+            } else {
+                "auto"
+            })
+    }
+
+    /// The language of a file, if it is overridden by a path glob, matched on
+    /// the path relative to the current directory.
+    ///
+    /// If several globs match, the last one is used.
+    #[must_use]
+    pub fn file_language(&self, file: &str) -> Option<&str> {
+        self.language_globs
+            .matches(relative_path(file))
+            .last()
+            .map(|&index| self.language_overrides[index].language.as_str())
+    }
+
+    /// The maximum number of suggestions per mistake.
+    ///
+    /// As with `languagetool-rust`, 0 means no limit.
+    #[must_use]
+    pub fn max_suggestions(&self) -> usize {
+        match self.max_suggestions.unwrap_or(DEFAULT_MAX_SUGGESTIONS) {
+            0 => usize::MAX,
+            max_suggestions => max_suggestions,
+        }
+    }
+
+    /// Returns true if the findings of the cached docs are needed, and not only
+    /// the findings of the docs which changed since the last run.
+    #[must_use]
+    pub fn needs_cached_findings(&self) -> bool {
+        // to report them, to compare them with a baseline, to fix them, or to fail
+        // the run on them. Only the human output leaves them out.
+        let is_gated = !self.deny.is_empty()
+            || self.max_findings.is_some()
+            || !self.max_category_findings.is_empty();
+
+        self.show_all
+            || self.baseline.is_some()
+            || self.write_baseline.is_some()
+            || self.fix
+            || self.interactive
+            || is_gated
+            || self.format() != Format::Human
+    }
+
+    /// The categories whose findings are fixed.
+    #[must_use]
+    pub fn fix_categories(&self) -> Vec<String> {
+        if self.fix_categories.is_empty() {
+            DEFAULT_FIX_CATEGORIES
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            self.fix_categories.clone()
+        }
+    }
+
+    /// The project dictionary.
+    #[must_use]
+    pub fn dictionary(&self) -> PathBuf {
+        self.dictionary
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DICTIONARY_FILE))
+    }
+
+    /// The length limit of the summaries of the docs.
+    #[must_use]
+    pub fn max_summary_length(&self) -> usize {
+        self.max_summary_length
+            .unwrap_or(DEFAULT_MAX_SUMMARY_LENGTH)
+    }
+
+    /// The project glossary.
+    #[must_use]
+    pub fn glossary(&self) -> PathBuf {
+        self.glossary
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(GLOSSARY_FILE))
+    }
+
+    /// The output format.
+    #[must_use]
+    pub fn format(&self) -> Format {
+        self.format
+            .or_else(|| self.message_format.map(Format::from))
+            .unwrap_or_default()
+    }
+
+    /// The severity of a match, overridden by rule id, then by category id.
+    #[must_use]
+    pub fn severity(&self, rule_id: &str, category_id: &str, issue_type: &str) -> Severity {
+        self.severities
+            .get(rule_id)
+            .or_else(|| self.severities.get(category_id))
+            .copied()
+            .unwrap_or_else(|| Severity::of_match(issue_type, category_id))
+    }
+
+    /// Returns true if a local rule is enabled, as `languagetool` enables its
+    /// rules with `--disable-rules`, `--disable-categories`, `--enable-rules`,
+    /// `--enable-categories` and `--enable-only`.
+    #[must_use]
+    pub fn is_local_rule_enabled(&self, rule_id: &str, category: &Categories) -> bool {
+        if self.disable_rules.iter().any(|id| id == rule_id)
+            || self.disable_categories.contains(category)
+        {
+            return false;
+        }
+
+        !self.enable_only
+            || self.enable_rules.iter().any(|id| id == rule_id)
+            || self.enable_categories.contains(category)
+    }
+
+    /// Returns true if the finding fails the run on its own.
+    #[must_use]
+    pub fn is_denied(&self, finding: &Finding) -> bool {
+        self.deny.iter().any(|id| {
+            id == &finding.rule_id || id == &finding.category_id || id == finding.severity.name()
+        })
+    }
+
+    /// Fills the options which are not set on the command line from a
+    /// configuration file.
+    ///
+    /// # Errors
+    /// If a path glob of the language overrides is invalid.
+    pub fn with_file(mut self, file: ConfigFile) -> Result<Self> {
+        if self.disable_categories.is_empty() {
+            self.disable_categories = file.disable_categories;
+        }
+        if self.enable_categories.is_empty() {
+            self.enable_categories = file.enable_categories;
+        }
+        if self.disable_rules.is_empty() {
+            self.disable_rules = file.disable_rules;
+        }
+        if self.enable_rules.is_empty() {
+            self.enable_rules = file.enable_rules;
+        }
+
+        self.enable_only |= file.enable_only;
+
+        self.language = self.language.or(file.language);
+        self.mother_tongue = self.mother_tongue.or(file.mother_tongue);
+        if self.preferred_variants.is_empty() {
+            self.preferred_variants = file.preferred_variants;
+        }
+        if self.alt_languages.is_empty() {
+            self.alt_languages = file.alt_languages;
+        }
+
+        if self.dicts.is_empty() {
+            self.dicts = file.dicts;
+        }
+
+        self.max_suggestions = self.max_suggestions.or(file.max_suggestions);
+
+        self.language_overrides = file.language_overrides;
+        let mut language_globs = GlobSetBuilder::new();
+        for language_override in &self.language_overrides {
+            language_globs.add(Glob::new(&language_override.path).wrap_err_with(|| {
+                format!(
+                    "invalid path glob of a language override: {}",
+                    language_override.path
+                )
+            })?);
+        }
+        self.language_globs = language_globs.build()?;
+        self.language_mismatch = self.language_mismatch.or(file.language_mismatch);
+
+        self.picky |= file.picky;
+
+        // the checker chosen on the command line replaces the one of the file.
+        if self.hunspell.is_none() && self.local_server.is_none() {
+            self.hunspell = file.hunspell;
+            self.local_server = file.local_server;
+        }
+        self.reuse_local_server |= file.reuse_local_server;
+
+        self.baseline = self.baseline.or(file.baseline);
+
+        if self.deny.is_empty() {
+            self.deny = file.deny;
+        }
+        self.max_findings = self.max_findings.or(file.max_findings);
+        self.max_summary_length = self.max_summary_length.or(file.max_summary_length);
+        self.doc_conventions |= file.doc_conventions;
+        self.max_category_findings = file.max_category_findings;
+
+        self.severities = file.severities;
+
+        if self.message_format.is_none() {
+            self.format = self.format.or(file.format);
+        }
+        self.output = self.output.or(file.output);
+        if self.fix_categories.is_empty() {
+            self.fix_categories = file.fix_categories;
+        }
+
+        self.dictionary = self.dictionary.or(file.dictionary);
+        self.glossary = self.glossary.or(file.glossary);
+
+        self.group_by = self.group_by.or(file.group_by);
+        self.sort = self.sort.or(file.sort);
+
+        Ok(self)
+    }
+}
+
 #[derive(Args)]
 #[command(version, about)]
 pub struct LanguageTool {
     #[clap(default_value = ".")]
     paths: Vec<PathBuf>,
 
+    #[clap(
+        long,
+        env = "LANGUAGETOOL_CONFIG",
+        help = "Configuration file. [default: languagetool.toml, if it exists]"
+    )]
+    config_file: Option<PathBuf>,
+
     #[clap(flatten)]
     config: Config,
 }
@@ -73,21 +472,40 @@ pub enum Cargo {
     LanguageTool(LanguageTool),
 }
 
-impl Cargo {
-    /// Run the command.
+impl LanguageTool {
+    /// The command line configuration, completed by the configuration file.
     ///
     /// # Errors
-    /// If an error occurs.
-    pub fn run(&self) -> Result<()> {
-        let Self::LanguageTool(cmd) = self;
+    /// If the configuration file cannot be read.
+    pub fn config(&self) -> Result<Config> {
+        let path = self
+            .config_file
+            .clone()
+            .or_else(|| Some(PathBuf::from(CONFIG_FILE)).filter(|path| path.is_file()));
 
-        let server = languagetool_rust::ServerClient::new(
-            &cmd.config.hostname,
-            cmd.config.port.as_deref().unwrap_or(""),
-        )
-        .with_max_suggestions(5);
+        let mut config = self.config.clone();
+        if let Some(path) = path {
+            config = config.with_file(ConfigFile::load(path)?)?;
+        }
 
-        let docs = cmd
+        config.show_all = config.needs_cached_findings();
+
+        Ok(config)
+    }
+}
+
+impl LanguageTool {
+    /// Checks the docs of the paths.
+    ///
+    /// # Errors
+    /// If an error occurs.
+    fn check<S: Checker, C: Cacheable>(
+        &self,
+        config: &Config,
+        checker: &S,
+        cache: &C,
+    ) -> Result<(Vec<String>, CheckResults)> {
+        let docs = self
             .paths
             .iter()
             .map(fetch_docs)
@@ -96,16 +514,287 @@ impl Cargo {
                 Ok(acc)
             })?;
 
-        let n_files = docs.len();
+        let files: Vec<_> = docs.iter().map(|(file, _)| file.clone()).collect();
+
+        Ok((files, check_files(checker, config, docs, cache)?))
+    }
+
+    /// Checks the docs with the checker, and reports the findings.
+    ///
+    /// # Errors
+    /// If an error occurs.
+    fn run_with<S: Checker, C: Cacheable>(
+        &self,
+        config: &Config,
+        checker: &S,
+        cache: &C,
+    ) -> Result<ExitCode> {
+        let (mut files, mut results) = self.check(config, checker, cache)?;
+
+        if config.interactive {
+            interactive::run(&results.findings, &config.dictionary())?;
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        if config.fix {
+            let pending_fixes = Fixes::new(&results.findings, &config.fix_categories());
+
+            if config.diff || config.patch_file.is_some() {
+                let patch = unified_diff(&pending_fixes.fixed_files()?);
+
+                if let Some(path) = &config.patch_file {
+                    std::fs::write(path, &patch)?;
+                }
+
+                if config.diff {
+                    print!("{patch}");
+                    return Ok(ExitCode::SUCCESS);
+                }
+            } else {
+                let fixed_files = pending_fixes.apply()?;
+
+                if !fixed_files.is_empty() {
+                    eprintln!(
+                        "Fixed {} findings in {} files.",
+                        fixed_files
+                            .iter()
+                            .map(|fixed_file| fixed_file.n_edits)
+                            .sum::<usize>(),
+                        fixed_files.len()
+                    );
+
+                    // the remaining findings are reported.
+                    (files, results) = self.check(config, checker, cache)?;
+                }
+            }
+        }
+
+        if let Some(path) = &config.write_baseline {
+            Baseline::from(results.findings.as_slice()).write(path)?;
+            println!(
+                "Recorded {} findings in {}.",
+                results.findings.len(),
+                path.display()
+            );
+            println!("Checked {} files.", files.len());
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let (mut findings, stale_entries) = if let Some(path) = &config.baseline {
+            Baseline::load(path)?.filter(results.findings)
+        } else {
+            (results.findings, vec![])
+        };
+
+        config
+            .sort
+            .unwrap_or_default()
+            .sort(&mut findings, config.group_by);
+
+        let report = Report {
+            files: &files,
+            n_docs: results.n_docs,
+            n_cached_docs: results.n_cached_docs,
+            findings: &findings,
+            stale_entries: &stale_entries,
+            group_by: config.group_by,
+        };
+
+        let format = config.format();
+        if let Some(path) = &config.output {
+            let mut output = BufWriter::new(File::create(path)?);
+            format.write(&report, &mut output, false)?;
+            output.flush()?;
+        } else {
+            format.write(&report, &mut std::io::stdout().lock(), true)?;
+        }
+
+        // the human output ends with the summary; keep it out of the other outputs.
+        if format != Format::Human {
+            eprintln!("{}", Summary::from(&report));
+        }
+
+        let failures = failures(config, &findings);
+        for failure in &failures {
+            eprintln!("error: {failure}");
+        }
+
+        Ok(if failures.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::from(FINDINGS_EXIT_CODE)
+        })
+    }
+}
+
+impl Cargo {
+    /// Run the command and returns its exit code.
+    ///
+    /// # Errors
+    /// If an error occurs.
+    pub fn run(&self) -> Result<ExitCode> {
+        let Self::LanguageTool(cmd) = self;
+
+        let config = cmd.config()?;
 
         let project_dir = directories::ProjectDirs::from("rnbguy", "github", "cargo-languagetool")
             .context("failed to get cache directory")?;
         let cache = SledCacheStore::new(project_dir.cache_dir())?;
 
-        check_and_annotate(&server, &cmd.config, docs, &cache)?;
+        if let Some(dictionary) = &config.hunspell {
+            let checker = HunspellChecker::new(dictionary, config.max_suggestions())?;
+            return cmd.run_with(&config, &checker, &cache);
+        }
+
+        let local_server = config
+            .local_server
+            .as_ref()
+            .map(|jar| {
+                LocalServer::start(jar, config.reuse_local_server, project_dir.data_local_dir())
+            })
+            .transpose()?;
+
+        let (hostname, port) = local_server.as_ref().map_or_else(
+            || {
+                (
+                    config.hostname.clone(),
+                    config.port.clone().unwrap_or_default(),
+                )
+            },
+            |local_server| {
+                (
+                    LocalServer::HOSTNAME.to_owned(),
+                    local_server.port().to_string(),
+                )
+            },
+        );
+
+        let server = Server::new(&hostname, &port, config.max_suggestions());
+
+        cmd.run_with(&config, &server, &cache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn hunspell_or_local_server() {
+        assert!(Config::try_parse_from([
+            "languagetool",
+            "--hunspell",
+            "en_US",
+            "--local-server",
+            "languagetool-server.jar",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn checker_of_the_command_line() {
+        let file = ConfigFile::parse(r#"hunspell = "en_US""#, Path::new("/project")).unwrap();
+        let config = Config::parse_from(["languagetool", "--local-server", "server.jar"])
+            .with_file(file)
+            .unwrap();
+
+        assert_eq!(config.local_server, Some(PathBuf::from("server.jar")));
+        assert_eq!(config.hunspell, None);
+    }
+
+    #[test]
+    fn file_languages() {
+        let file = ConfigFile::parse(
+            "[[language_overrides]]\npath = \"src/de/**\"\nlanguage = \"de-DE\"\n\n[[language_overrides]]\npath = \"src/de/fr/**\"\nlanguage = \"fr\"",
+            Path::new(""),
+        )
+        .unwrap();
+        let config = Config::parse_from(["languagetool"])
+            .with_file(file)
+            .unwrap();
+
+        assert_eq!(config.file_language("src/lib.rs"), None);
+        assert_eq!(config.file_language("./src/de/lib.rs"), Some("de-DE"));
+        assert_eq!(config.file_language("src/de/fr/lib.rs"), Some("fr"));
+
+        let absolute = std::env::current_dir().unwrap().join("src/de/lib.rs");
+        assert_eq!(
+            config.file_language(&absolute.to_string_lossy()),
+            Some("de-DE")
+        );
+    }
+
+    #[test]
+    fn invalid_globs() {
+        let file = ConfigFile::parse(
+            "[[language_overrides]]\npath = \"src/[de\"\nlanguage = \"de-DE\"",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert!(Config::parse_from(["languagetool"])
+            .with_file(file)
+            .is_err());
+    }
+
+    #[test]
+    fn cached_findings() {
+        let needs_cached_findings = |args: &[&str]| {
+            Config::parse_from([&["languagetool"], args].concat()).needs_cached_findings()
+        };
+
+        assert!(!needs_cached_findings(&[]));
+        assert!(needs_cached_findings(&["--show-all"]));
+        assert!(needs_cached_findings(&["--deny", "TYPOS"]));
+        assert!(needs_cached_findings(&["--max-findings", "0"]));
+        assert!(needs_cached_findings(&["--format", "sarif"]));
+        assert!(needs_cached_findings(&["--message-format", "json"]));
+        assert!(!needs_cached_findings(&["--format", "human"]));
+
+        let file = ConfigFile::parse("[max_category_findings]\nTYPOS = 0", Path::new("")).unwrap();
+        assert!(Config::parse_from(["languagetool"])
+            .with_file(file)
+            .unwrap()
+            .needs_cached_findings());
+    }
+
+    #[test]
+    fn max_suggestions() {
+        let max_suggestions = |args: &[&str]| {
+            Config::parse_from([&["languagetool"], args].concat()).max_suggestions()
+        };
+
+        assert_eq!(max_suggestions(&[]), 5);
+        assert_eq!(max_suggestions(&["--max-suggestions", "2"]), 2);
+        assert_eq!(max_suggestions(&["--max-suggestions", "0"]), usize::MAX);
+    }
 
-        println!("Checked {n_files} files.");
+    #[test]
+    fn severity_overrides() {
+        let mut config = Config::parse_from(["languagetool"]);
+        config.severities = BTreeMap::from([
+            ("EN_A_VS_AN".to_owned(), Severity::Note),
+            ("GRAMMAR".to_owned(), Severity::Warning),
+        ]);
 
-        Ok(())
+        // the rule id first, then the category id, then the issue type.
+        assert_eq!(
+            config.severity("EN_A_VS_AN", "GRAMMAR", "grammar"),
+            Severity::Note
+        );
+        assert_eq!(
+            config.severity("HE_VERB_AGR", "GRAMMAR", "grammar"),
+            Severity::Warning
+        );
+        assert_eq!(
+            config.severity("MORFOLOGIK_RULE_EN_US", "TYPOS", "style"),
+            Severity::Warning
+        );
+        assert_eq!(
+            config.severity("MORFOLOGIK_RULE_EN_US", "TYPOS", ""),
+            Severity::Error
+        );
     }
 }
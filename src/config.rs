@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use serde::Deserialize;
+
+use crate::finding::Severity;
+use crate::languagetool::categories::Categories;
+use crate::report::{Format, GroupBy, SortBy};
+
+/// The configuration file looked up in the current directory.
+pub const CONFIG_FILE: &str = "languagetool.toml";
+
+/// How to report docs whose detected language differs from the configured one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageMismatch {
+    Off,
+    #[default]
+    Warn,
+    Error,
+}
+
+/// Language of the files matching a glob.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LanguageOverride {
+    pub path: String,
+    pub language: String,
+}
+
+/// Options read from a configuration file.
+///
+/// These are used for the options which are not set on the command line.
+#[allow(
+    clippy::struct_excessive_bools,
+    reason = "This is a configuration struct."
+)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub disable_categories: Vec<Categories>,
+    pub enable_categories: Vec<Categories>,
+
+    pub disable_rules: Vec<String>,
+    pub enable_rules: Vec<String>,
+
+    pub enable_only: bool,
+
+    pub language: Option<String>,
+    pub mother_tongue: Option<String>,
+    pub preferred_variants: Vec<String>,
+    pub alt_languages: Vec<String>,
+
+    pub dicts: Vec<String>,
+
+    pub max_suggestions: Option<usize>,
+
+    pub language_overrides: Vec<LanguageOverride>,
+    pub language_mismatch: Option<LanguageMismatch>,
+
+    pub picky: bool,
+
+    pub hunspell: Option<PathBuf>,
+
+    pub local_server: Option<PathBuf>,
+    pub reuse_local_server: bool,
+
+    pub baseline: Option<PathBuf>,
+
+    pub deny: Vec<String>,
+    pub max_findings: Option<usize>,
+    pub max_category_findings: BTreeMap<String, usize>,
+
+    pub severities: BTreeMap<String, Severity>,
+
+    pub format: Option<Format>,
+    pub output: Option<PathBuf>,
+    pub fix_categories: Vec<String>,
+
+    pub dictionary: Option<PathBuf>,
+    pub glossary: Option<PathBuf>,
+    pub max_summary_length: Option<usize>,
+    pub doc_conventions: bool,
+
+    pub group_by: Option<GroupBy>,
+    pub sort: Option<SortBy>,
+}
+
+impl ConfigFile {
+    /// Reads a configuration file.
+    ///
+    /// # Errors
+    /// If the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        Self::parse(&std::fs::read_to_string(path)?, dir)
+    }
+
+    /// Parses a configuration file, whose relative `hunspell` and
+    /// `local_server` paths are relative to `dir`, the directory of the file.
+    ///
+    /// # Errors
+    /// If the file cannot be parsed, or sets both `hunspell` and `local_server`.
+    pub fn parse(text: &str, dir: &Path) -> Result<Self> {
+        let mut file: Self = toml::from_str(text)?;
+
+        if file.hunspell.is_some() && file.local_server.is_some() {
+            bail!("hunspell and local_server cannot be set together");
+        }
+
+        for path in [&mut file.hunspell, &mut file.local_server]
+            .into_iter()
+            .flatten()
+        {
+            if path.is_relative() {
+                *path = dir.join(&*path);
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_relative_to_the_file() {
+        let file = ConfigFile::parse(
+            r#"local_server = "tools/languagetool-server.jar""#,
+            Path::new("/project"),
+        )
+        .unwrap();
+        assert_eq!(
+            file.local_server,
+            Some(PathBuf::from("/project/tools/languagetool-server.jar"))
+        );
+
+        let file = ConfigFile::parse(
+            r#"hunspell = "/usr/share/hunspell/en_US""#,
+            Path::new("/project"),
+        )
+        .unwrap();
+        assert_eq!(
+            file.hunspell,
+            Some(PathBuf::from("/usr/share/hunspell/en_US"))
+        );
+    }
+
+    #[test]
+    fn hunspell_or_local_server() {
+        assert!(ConfigFile::parse(
+            "hunspell = \"en_US\"\nlocal_server = \"languagetool-server.jar\"",
+            Path::new("")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! The `dictionary` module contains the project dictionary: the words which are not
+//! misspellings, one per line.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+use color_eyre::Result;
+
+/// The project dictionary, if the file exists.
+pub const DICTIONARY_FILE: &str = "languagetool-dictionary.txt";
+
+/// Category of the findings which are dropped for the words of the dictionary.
+const SPELLING_CATEGORY: &str = "TYPOS";
+
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary(HashSet<String>);
+
+impl Dictionary {
+    /// Reads a dictionary. A missing file is an empty dictionary.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    /// If the file exists but cannot be read.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self(
+            std::fs::read_to_string(path)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(ToOwned::to_owned)
+                .collect(),
+        ))
+    }
+
+    /// Appends a word to a dictionary file.
+    ///
+    /// # Errors
+    /// If the file cannot be written.
+    pub fn add(path: impl AsRef<Path>, word: &str) -> Result<()> {
+        let path = path.as_ref();
+
+        // the last line of a file edited by hand may have no newline.
+        let ends_with_newline =
+            !path.is_file() || matches!(std::fs::read(path)?.last(), None | Some(b'\n'));
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        if !ends_with_newline {
+            writeln!(file)?;
+        }
+        writeln!(file, "{word}")?;
+        Ok(())
+    }
+
+    /// Returns true if the words of the findings of the category can be
+    /// added to the dictionary.
+    #[must_use]
+    pub fn is_spelling(category_id: &str) -> bool {
+        category_id == SPELLING_CATEGORY
+    }
+
+    /// Returns true if a finding of the category is about a word of the dictionary.
+    #[must_use]
+    pub fn allows(&self, category_id: &str, matched_text: &str) -> bool {
+        Self::is_spelling(category_id) && self.0.contains(matched_text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_words() {
+        let dir =
+            std::env::temp_dir().join(format!("languagetool-dictionary-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let new = dir.join("new.txt");
+        Dictionary::add(&new, "teh").unwrap();
+        Dictionary::add(&new, "wierd").unwrap();
+        assert_eq!(std::fs::read_to_string(&new).unwrap(), "teh\nwierd\n");
+
+        let unterminated = dir.join("unterminated.txt");
+        std::fs::write(&unterminated, "# words\nfoo").unwrap();
+        Dictionary::add(&unterminated, "teh").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&unterminated).unwrap(),
+            "# words\nfoo\nteh\n"
+        );
+
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, "").unwrap();
+        Dictionary::add(&empty, "teh").unwrap();
+        assert_eq!(std::fs::read_to_string(&empty).unwrap(), "teh\n");
+
+        let dictionary = Dictionary::load(&unterminated).unwrap();
+        assert!(dictionary.allows("TYPOS", "foo"));
+        assert!(dictionary.allows("TYPOS", "teh"));
+        assert!(!dictionary.allows("GRAMMAR", "teh"));
+        assert!(!dictionary.allows("TYPOS", "# words"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
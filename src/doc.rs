@@ -1,36 +1,119 @@
 //! The `docs` module contains all the necessary stuff to work with doc comments.
 
-use annotate_snippets::{Level, Renderer, Snippet};
 use color_eyre::{Report, Result};
-use languagetool_rust::check::Level as LanguageToolLevel;
+use languagetool_rust::check::{DetectedLanguage, Level as LanguageToolLevel};
 use languagetool_rust::CheckResponse;
 use log::debug;
-use proc_macro2::{LineColumn, Literal, Span, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, LineColumn, Literal, Span, TokenStream, TokenTree};
 
 use crate::cache::Cacheable;
+use crate::checker::{Checker, Request};
 use crate::cli::Config;
+use crate::finding::{Finding, FindingContext, Position, Severity};
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::local_response;
+use crate::rules::{is_local_rule, Rules};
+use crate::suppression::Suppressions;
+
+/// Minimum confidence of a detected language to report a language mismatch.
+const LANGUAGE_MISMATCH_CONFIDENCE: f64 = 0.9;
+
+/// Id of the language mismatch diagnostics.
+pub const LANGUAGE_MISMATCH_ID: &str = "LANGUAGE_MISMATCH";
+
+/// Prefix of the directives in inner doc comments, e.g. `//! languagetool: language=de-DE`.
+const DIRECTIVE_PREFIX: &str = "languagetool:";
+
+/// Returns the language set by a directive.
+#[must_use]
+pub fn language_directive(text: &str) -> Option<String> {
+    text.trim()
+        .strip_prefix(DIRECTIVE_PREFIX)?
+        .split([' ', ','])
+        .find_map(|setting| setting.strip_prefix("language="))
+        .map(ToOwned::to_owned)
+}
 
 #[derive(Debug, Clone)]
-pub struct RawDocs(Vec<Literal>);
+pub struct RawDoc {
+    pub literal: Literal,
+    /// Language set by a directive in the enclosing module.
+    pub language: Option<String>,
+    /// Path of the documented item in the file, e.g. `Foo::bar`.
+    pub item_path: String,
+    /// Whether the documented item is a function.
+    pub is_fn: bool,
+    /// Rules and categories allowed in the enclosing items.
+    pub allowed: Vec<String>,
+}
+
+impl From<Literal> for RawDoc {
+    fn from(literal: Literal) -> Self {
+        Self {
+            literal,
+            language: None,
+            item_path: String::new(),
+            is_fn: false,
+            allowed: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RawDocs(pub Vec<RawDoc>);
 
 impl From<TokenStream> for RawDocs {
     fn from(stream: TokenStream) -> Self {
         let mut docs = vec![];
+        let mut language = None;
         let mut is_doc = false;
+        let mut last_punct = None;
+        let mut is_inner_attribute = false;
         for tree in stream {
             match tree {
                 TokenTree::Ident(ident) => is_doc = ident == "doc",
                 TokenTree::Group(group) => {
-                    docs.append(&mut Self::from(group.stream()).0);
+                    let mut group_docs = Self::from(group.stream()).0;
+
+                    // `//!` comments are inner attributes, which may hold a directive for the enclosing module.
+                    if is_inner_attribute && group.delimiter() == Delimiter::Bracket {
+                        if let [doc] = group_docs.as_slice() {
+                            if let Some(directive_language) =
+                                serde_json::from_str::<String>(&doc.literal.to_string())
+                                    .ok()
+                                    .as_deref()
+                                    .and_then(language_directive)
+                            {
+                                language = Some(directive_language);
+                                group_docs.clear();
+                            }
+                        }
+                    }
+
+                    docs.append(&mut group_docs);
                 }
                 TokenTree::Literal(literal) => {
                     if is_doc {
-                        docs.push(literal);
+                        docs.push(RawDoc::from(literal));
                     }
                 }
-                TokenTree::Punct(_) => {}
+                TokenTree::Punct(punct) => {
+                    is_inner_attribute = punct.as_char() == '!' && last_punct == Some('#');
+                    last_punct = Some(punct.as_char());
+                    continue;
+                }
             };
+            last_punct = None;
+            is_inner_attribute = false;
+        }
+
+        // directives in nested modules take precedence.
+        for doc in &mut docs {
+            if doc.language.is_none() {
+                doc.language.clone_from(&language);
+            }
         }
+
         Self(docs)
     }
 }
@@ -77,7 +160,17 @@ impl From<Span> for DocSpan {
 #[derive(Debug, Clone)]
 pub struct Doc {
     pub text: Vec<(String, DocSpan)>,
+    /// Language of the doc, if it differs from the configured one.
+    pub language: Option<String>,
+    /// Path of the documented item in the file, e.g. `Foo::bar`.
+    pub item_path: String,
+    /// Whether the documented item is a function.
+    pub is_fn: bool,
+    /// Rules and categories allowed for the documented item.
+    pub allowed: Vec<String>,
     pub check_response: Option<CheckResponse>,
+    /// Whether the check response came from the cache.
+    pub cached: bool,
 }
 
 impl core::fmt::Display for Doc {
@@ -94,13 +187,34 @@ impl core::fmt::Display for Doc {
 }
 
 impl Doc {
+    /// Creates a doc block from its first line.
+    fn new(line: String, span: DocSpan, raw_doc: &RawDoc) -> Self {
+        Self {
+            text: vec![(line, span)],
+            language: raw_doc.language.clone(),
+            item_path: raw_doc.item_path.clone(),
+            is_fn: raw_doc.is_fn,
+            allowed: raw_doc.allowed.clone(),
+            check_response: None,
+            cached: false,
+        }
+    }
+
+    /// The language of the doc.
+    #[must_use]
+    pub fn language<'a>(&'a self, config: &'a Config) -> &'a str {
+        self.language
+            .as_deref()
+            .unwrap_or_else(|| config.language())
+    }
+
     /// Checks a doc.
     ///
     /// # Errors
     /// If an error occurred.
-    pub fn checked<C: Cacheable>(
+    pub fn checked<S: Checker, C: Cacheable>(
         &mut self,
-        server: &languagetool_rust::ServerClient,
+        checker: &S,
         config: &Config,
         cache: &C,
     ) -> Result<()> {
@@ -112,32 +226,54 @@ impl Doc {
             check_request.api_key = Some(api_key.clone());
         }
 
-        check_request.language.clone_from(&config.language);
+        self.language(config)
+            .clone_into(&mut check_request.language);
+
+        check_request
+            .mother_tongue
+            .clone_from(&config.mother_tongue);
+
+        if !config.preferred_variants.is_empty() {
+            check_request.preferred_variants = Some(config.preferred_variants.clone());
+        }
+
+        if !config.dicts.is_empty() {
+            check_request.dicts = Some(config.dicts.clone());
+        }
 
         if config.picky {
             check_request.level = LanguageToolLevel::Picky;
         }
 
-        check_request.enabled_categories = Some(
-            config
-                .enable_categories
-                .iter()
-                .map(ToString::to_string)
-                .collect(),
-        );
+        // The local rules and categories are unknown to `languagetool`.
+        let enabled_categories: Vec<String> = config
+            .enable_categories
+            .iter()
+            .filter(|category| !category.is_local())
+            .map(ToString::to_string)
+            .collect();
+
+        let enabled_rules: Vec<String> = config
+            .enable_rules
+            .iter()
+            .filter(|rule_id| !is_local_rule(rule_id))
+            .map(ToString::to_string)
+            .collect();
+
+        if config.enable_only && enabled_categories.is_empty() && enabled_rules.is_empty() {
+            // Only local rules are enabled.
+            self.check_response = Some(local_response(self.language(config), &[])?);
+            return Ok(());
+        }
 
-        check_request.enabled_rules = Some(
-            config
-                .enable_rules
-                .iter()
-                .map(ToString::to_string)
-                .collect(),
-        );
+        check_request.enabled_categories = Some(enabled_categories);
+        check_request.enabled_rules = Some(enabled_rules);
 
         check_request.disabled_categories = Some(
             config
                 .disable_categories
                 .iter()
+                .filter(|category| !category.is_local())
                 .map(ToString::to_string)
                 .collect(),
         );
@@ -146,174 +282,279 @@ impl Doc {
             config
                 .disable_rules
                 .iter()
+                .filter(|rule_id| !is_local_rule(rule_id))
                 .map(ToString::to_string)
                 .collect(),
         );
 
         check_request.enabled_only = config.enable_only;
 
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_time()
-            .enable_io()
-            .build()?;
-
-        // doc.check_response = Some(rt.block_on(async { server.check(&check_request).await })?);
-
-        if config.no_cache {
-            self.check_response = Some({
-                cache.set_and_get(&check_request, |req| {
-                    Ok(rt.block_on(async { server.check(req).await })?)
-                })?
-            });
-        } else if config.show_all || !cache.hits(&check_request)? {
-            self.check_response = Some({
-                cache.get_or(&check_request, |req| {
-                    Ok(rt.block_on(async { server.check(req).await })?)
-                })?
-            });
+        let check_request = Request {
+            check_request,
+            alt_languages: (!config.alt_languages.is_empty())
+                .then(|| config.alt_languages.join(",")),
+        };
+
+        if !checker.is_cacheable() {
+            self.check_response = Some(checker.check(&check_request)?);
+        } else if config.no_cache {
+            self.check_response =
+                Some(cache.set_and_get(&check_request, |req| checker.check(req))?);
         } else {
-            // !config.no_cache && !config.show_all && cache_db.hits(&check_request)?
-            // we don't print the result.
+            self.cached = cache.hits(&check_request)?;
+            if config.show_all || !self.cached {
+                self.check_response = Some(cache.get_or(&check_request, |req| checker.check(req))?);
+            }
+            // otherwise, we don't print the result of a cached check.
         }
 
         Ok(())
     }
 
-    /// Transform annotations for raw source.
-    pub fn transform_matches(&mut self, source: &str) {
-        let doc_str = self.to_string();
-        if let Some(check_response) = self.check_response.as_mut() {
-            for each_match in &mut check_response.matches {
-                // ensured by LT API.
-                // assert_eq!(each_match.length, each_match.context.length);
-
-                let row = doc_str
-                    .chars()
-                    .take(each_match.offset)
-                    .filter(|chr| chr == &'\n')
-                    .count();
-
-                let (_line, span) = &self.text[row];
-
-                let doc_prev_line_end_offset = doc_str
-                    .lines()
-                    .take(row)
-                    .map(|st| st.len() + 1) // because of extra space
-                    .sum::<usize>();
+    /// Adds the matches of the local rules to the check response.
+    ///
+    /// # Errors
+    /// If a match cannot be built.
+    pub fn check_rules(&mut self, rules: &Rules) -> Result<()> {
+        if self.check_response.is_none() {
+            return Ok(());
+        }
 
-                // offset of the match in the line in doc comments
-                let doc_line_offset = each_match.offset - doc_prev_line_end_offset;
+        let text = self.to_string();
+        let rule_matches: Vec<_> = rules
+            .iter()
+            .map(|rule| (rule.covers_misspellings(), rule.check(self)))
+            .collect();
+
+        let Some(check_response) = self.check_response.as_mut() else {
+            return Ok(());
+        };
+
+        let typos = Categories::Typos.to_string();
+
+        for (covers_misspellings, local_matches) in rule_matches {
+            if covers_misspellings {
+                check_response.matches.retain(|each_match| {
+                    each_match.rule.category.id != typos
+                        || !local_matches.iter().any(|local_match| {
+                            each_match.offset < local_match.offset + local_match.length
+                                && local_match.offset < each_match.offset + each_match.length
+                        })
+                });
+            }
 
-                let line_row = span.start.line;
-                let line_offset = span.start.column + 3 + doc_line_offset; // because of rust comment tags
+            for local_match in local_matches {
+                check_response.matches.push(local_match.into_match(&text)?);
+            }
+        }
 
-                // line beginning in the file
-                let line_begin_offset = source
-                    .lines()
-                    .take(line_row - 1)
-                    .map(|st| st.len() + 1)
-                    .sum::<usize>();
+        Ok(())
+    }
 
-                let doc_match_offset = each_match.offset;
+    /// Transforms the offsets of the matches, in chars in the doc, into byte
+    /// offsets in the raw source.
+    ///
+    /// The context of each match becomes the source lines of the match, with
+    /// the byte offset of the match in them.
+    pub fn transform_matches(&mut self, source: &str) {
+        let line_offsets = line_offsets(&self.text, source);
+
+        let Some(check_response) = self.check_response.as_mut() else {
+            return;
+        };
+
+        for each_match in &mut check_response.matches {
+            let start = source_offset(&self.text, &line_offsets, each_match.offset);
+            let end = source_offset(
+                &self.text,
+                &line_offsets,
+                each_match.offset + each_match.length,
+            );
+
+            // the context starts at the line of the match, and ends at the end
+            // of the line of the end of the match.
+            let context_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+            let context_end = source[end..]
+                .find('\n')
+                .map_or(source.len(), |index| end + index + 1);
+
+            each_match.offset = start;
+            each_match.length = end - start;
+
+            source[context_start..context_end].clone_into(&mut each_match.context.text);
+            each_match.context.offset = start - context_start;
+            each_match.context.length = end - start;
+        }
+    }
 
-                // updating value
-                each_match.offset = line_begin_offset + line_offset;
+    /// Returns true if the rule or category is allowed for the documented item.
+    #[must_use]
+    pub fn is_allowed(&self, rule_id: &str, category_id: &str) -> bool {
+        self.allowed
+            .iter()
+            .any(|id| id == rule_id || id == category_id)
+    }
 
-                // LT context starts at: each_match.offset - each_match.context.offset
-                // start the context from the same line as the beginning of the match.
-                each_match.context.offset = line_offset; // this gets changed too
+    /// Drops the matches suppressed by directives in the source, or allowed for
+    /// the documented item.
+    ///
+    /// The matches must be transformed for the raw source.
+    pub fn suppress(&mut self, source: &str, suppressions: &Suppressions) {
+        if let Some(check_response) = self.check_response.as_mut() {
+            check_response.matches.retain(|each_match| {
+                if self
+                    .allowed
+                    .iter()
+                    .any(|id| id == &each_match.rule.id || id == &each_match.rule.category.id)
+                {
+                    debug!("Allowed: {each_match:?}");
+                    return false;
+                }
 
-                // end the context at the end of the line of the end of the match.
+                let line = Position::of_byte_offset(source, each_match.offset).line;
 
-                let mut new_context_length = 0;
-                let mut length_delta = 0;
-                let mut match_count = doc_prev_line_end_offset;
+                let is_suppressed = suppressions.is_suppressed(
+                    line,
+                    &each_match.rule.id,
+                    &each_match.rule.category.id,
+                );
 
-                for (doc_line, file_line) in doc_str
-                    .lines()
-                    .skip(row)
-                    .zip(source.lines().skip(line_row - 1))
-                {
-                    new_context_length += file_line.len() + 1; // because of newline
-                    match_count += doc_line.len() + 1; // because of newline
-                    if doc_match_offset + each_match.length < match_count {
-                        break;
-                    }
-                    length_delta += span.start.column + 3; // because of rust comment tags
+                if is_suppressed {
+                    debug!("Suppressed: {each_match:?}");
                 }
 
-                each_match.length += length_delta;
-                each_match.context.length = each_match.length;
-
-                source[line_begin_offset..][..new_context_length]
-                    .clone_into(&mut each_match.context.text);
-            }
+                !is_suppressed
+            });
         }
     }
 
-    /// Annotate the doc with the check response.
+    /// Returns the findings of the check response.
     ///
-    /// # Errors
-    /// If an error occurs.
-    pub fn annotate(&self, file: &str, source: &str) {
-        if let Some(check_response) = self.check_response.as_ref() {
-            debug!("Annotating: {}", file);
-
-            check_response.matches.iter().for_each(|each_match| {
-                debug!("Annotating: {:?}", each_match);
-
-                let replacements = each_match.replacements.iter().fold(
-                    String::new(),
-                    |mut joined_string, replacement| {
-                        if !joined_string.is_empty() {
-                            joined_string.push_str(", ");
-                        }
-                        joined_string.push_str(&replacement.value);
-                        joined_string
+    /// The matches must be transformed for the raw source.
+    #[must_use]
+    pub fn findings(&self, file: &str, source: &str, config: &Config) -> Vec<Finding> {
+        let Some(check_response) = self.check_response.as_ref() else {
+            return vec![];
+        };
+
+        check_response
+            .matches
+            .iter()
+            .map(|each_match| {
+                debug!("Finding: {each_match:?}");
+
+                let byte_range = each_match.offset..each_match.offset + each_match.length;
+
+                Finding {
+                    file: file.to_owned(),
+                    item_path: self.item_path.clone(),
+                    rule_id: each_match.rule.id.clone(),
+                    category_id: each_match.rule.category.id.clone(),
+                    message: each_match.message.clone(),
+                    description: each_match.rule.description.clone(),
+                    replacements: each_match
+                        .replacements
+                        .iter()
+                        .map(|replacement| replacement.value.clone())
+                        .collect(),
+                    urls: each_match
+                        .rule
+                        .urls
+                        .iter()
+                        .flatten()
+                        .map(|url| url.value.clone())
+                        .collect(),
+                    matched_text: source
+                        .get(byte_range.clone())
+                        .unwrap_or_default()
+                        .to_owned(),
+                    start: Position::of_byte_offset(source, byte_range.start),
+                    end: Position::of_byte_offset(source, byte_range.end),
+                    byte_range,
+                    severity: config.severity(
+                        &each_match.rule.id,
+                        &each_match.rule.category.id,
+                        &each_match.rule.issue_type,
+                    ),
+                    sentence: each_match.sentence.clone(),
+                    context: FindingContext {
+                        text: each_match.context.text.clone(),
+                        offset: each_match.context.offset,
+                        length: each_match.context.length,
                     },
-                );
+                    cached: self.cached,
+                }
+            })
+            .collect()
+    }
+}
 
-                let snippet = Snippet::source(&each_match.context.text)
-                    .line_start(
-                        1 + source
-                            .chars()
-                            .take(each_match.offset)
-                            .filter(|chr| chr == &'\n')
-                            .count(),
-                    )
-                    .origin(file)
-                    .fold(true)
-                    .annotation(
-                        Level::Error
-                            .span(
-                                each_match.context.offset
-                                    ..each_match.context.offset + each_match.context.length,
-                            )
-                            .label(&each_match.rule.description),
-                    )
-                    .annotation(
-                        Level::Help
-                            .span(
-                                each_match.context.offset
-                                    ..each_match.context.offset + each_match.context.length,
-                            )
-                            .label(&replacements),
-                    );
-
-                let message_id = format!("{}:{}", each_match.rule.id, each_match.rule.category.id);
-
-                let message = Level::Error
-                    .title(&each_match.message)
-                    .id(&message_id)
-                    .snippet(snippet);
-
-                let renderer = Renderer::styled();
-
-                let annotation = renderer.render(message).to_string();
-
-                println!("{annotation}");
-            });
-        }
+impl Doc {
+    /// Returns the detected language, if it confidently differs from `language`.
+    #[must_use]
+    pub fn language_mismatch(&self, language: &str) -> Option<&DetectedLanguage> {
+        let detected_language = &self.check_response.as_ref()?.language.detected_language;
+
+        let primary_language =
+            |code: &str| code.split('-').next().unwrap_or(code).to_ascii_lowercase();
+
+        (language != "auto"
+            && primary_language(language) != primary_language(&detected_language.code)
+            && detected_language
+                .confidence
+                .is_some_and(|confidence| confidence >= LANGUAGE_MISMATCH_CONFIDENCE))
+        .then_some(detected_language)
+    }
+
+    /// Returns a finding for the doc block if its detected language differs
+    /// from `language`.
+    #[must_use]
+    pub fn language_mismatch_finding(
+        &self,
+        file: &str,
+        source: &str,
+        language: &str,
+        severity: Severity,
+    ) -> Option<Finding> {
+        let detected_language = self.language_mismatch(language)?;
+
+        let line_offsets = line_offsets(&self.text, source);
+        let length = self.to_string().chars().count();
+
+        // the doc block, from the start of its text to the end of its last line.
+        let byte_range = *line_offsets.first()?..source_offset(&self.text, &line_offsets, length);
+
+        debug!("Language mismatch: {detected_language:?}");
+
+        let context_start = source[..byte_range.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+
+        Some(Finding {
+            file: file.to_owned(),
+            item_path: self.item_path.clone(),
+            rule_id: LANGUAGE_MISMATCH_ID.to_owned(),
+            category_id: Categories::Misc.to_string(),
+            message: format!("The language of the doc differs from {language}."),
+            description: format!(
+                "detected {} ({})",
+                detected_language.name, detected_language.code
+            ),
+            replacements: vec![],
+            urls: vec![],
+            matched_text: detected_language.code.clone(),
+            start: Position::of_byte_offset(source, byte_range.start),
+            end: Position::of_byte_offset(source, byte_range.end),
+            severity,
+            sentence: self.to_string(),
+            context: FindingContext {
+                text: source[context_start..byte_range.end].to_owned(),
+                offset: byte_range.start - context_start,
+                length: byte_range.len(),
+            },
+            byte_range,
+            cached: self.cached,
+        })
     }
 }
 
@@ -323,6 +564,51 @@ pub struct Docs {
     pub fixed: Vec<Doc>,
 }
 
+/// Returns the byte offset in the source of the start of each line of a doc.
+fn line_offsets(text: &[(String, DocSpan)], source: &str) -> Vec<usize> {
+    let line_starts: Vec<usize> = core::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+
+    text.iter()
+        .map(|(_, span)| {
+            let line_start = line_starts
+                .get(span.start.line - 1)
+                .copied()
+                .unwrap_or(source.len());
+            let line = source[line_start..].split('\n').next().unwrap_or_default();
+
+            // the columns count chars, and the text starts after the rust comment tags.
+            line_start
+                + line
+                    .char_indices()
+                    .nth(span.start.column + 3)
+                    .map_or(line.len(), |(index, _)| index)
+        })
+        .collect()
+}
+
+/// Returns the byte offset in the source of a char offset in the text of a doc.
+fn source_offset(text: &[(String, DocSpan)], line_offsets: &[usize], char_offset: usize) -> usize {
+    let mut remaining = char_offset;
+
+    for ((line, _), line_offset) in text.iter().zip(line_offsets) {
+        let length = line.chars().count();
+        if remaining <= length {
+            return line_offset
+                + line
+                    .char_indices()
+                    .nth(remaining)
+                    .map_or(line.len(), |(index, _)| index);
+        }
+        remaining -= length + 1; // because of newline
+    }
+
+    text.last()
+        .zip(line_offsets.last())
+        .map_or(0, |((line, _), line_offset)| line_offset + line.len())
+}
+
 impl TryFrom<RawDocs> for Docs {
     type Error = Report;
 
@@ -331,8 +617,8 @@ impl TryFrom<RawDocs> for Docs {
             Vec::new(),
             |mut fixed_docs: Vec<Doc>, doc| {
                 let (original_string, span) = {
-                    let original_string: String = serde_json::from_str(&doc.to_string())?;
-                    let mut span: DocSpan = doc.span().into();
+                    let original_string: String = serde_json::from_str(&doc.literal.to_string())?;
+                    let mut span: DocSpan = doc.literal.span().into();
                     match original_string.strip_prefix(' ') {
                         Some(fixed_string) => {
                             span.start.column += 1; // because, leading space is trimmed.
@@ -346,22 +632,21 @@ impl TryFrom<RawDocs> for Docs {
                     // If the lines are consecutive, then these two doc comments belong to a single block.
 
                     if let Some(last_line) = last.text.last() {
-                        if span.start.line - last_line.1.end.line == 1 {
+                        if span.start.line - last_line.1.end.line == 1
+                            && last.language == doc.language
+                            && last.item_path == doc.item_path
+                            && last.is_fn == doc.is_fn
+                            && last.allowed == doc.allowed
+                        {
                             last.text.push((original_string, span));
                         } else {
-                            fixed_docs.push(Doc {
-                                text: vec![(original_string, span)],
-                                check_response: None,
-                            });
+                            fixed_docs.push(Doc::new(original_string, span, doc));
                         }
                     } else {
                         // unreachable!()
                     }
                 } else {
-                    fixed_docs.push(Doc {
-                        text: vec![(original_string, span)],
-                        check_response: None,
-                    });
+                    fixed_docs.push(Doc::new(original_string, span, doc));
                 }
 
                 Ok(fixed_docs)
@@ -371,3 +656,274 @@ impl TryFrom<RawDocs> for Docs {
         Ok(Self { original, fixed })
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use core::cell::RefCell;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::languagetool::local::LocalMatch;
+
+    /// Returns the docs of a source.
+    ///
+    /// # Panics
+    /// If the source cannot be parsed.
+    #[must_use]
+    pub fn docs(source: &str) -> Vec<Doc> {
+        let file = syn::parse_file(source).unwrap();
+        Docs::try_from(RawDocs::from(&file)).unwrap().fixed
+    }
+
+    /// Returns the findings of local matches of words in the docs of a source.
+    fn findings(source: &str, words: &[&str]) -> Vec<Finding> {
+        let config = Config::parse_from(["languagetool"]);
+
+        docs(source)
+            .iter_mut()
+            .flat_map(|doc| {
+                let text = doc.to_string();
+                let matches: Vec<_> = words
+                    .iter()
+                    .filter_map(|word| {
+                        let offset = text.find(word)?;
+                        LocalMatch {
+                            offset: text[..offset].chars().count(),
+                            length: word.chars().count(),
+                            message: String::new(),
+                            short_message: String::new(),
+                            replacements: vec![],
+                            rule_id: "RULE".to_owned(),
+                            rule_description: String::new(),
+                            issue_type: "misspelling".to_owned(),
+                            category_id: "TYPOS".to_owned(),
+                            category_name: String::new(),
+                        }
+                        .into_match(&text)
+                        .ok()
+                    })
+                    .collect();
+
+                doc.check_response = Some(local_response("en-US", &matches).unwrap());
+                doc.transform_matches(source);
+                doc.findings("lib.rs", source, &config)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn findings_after_non_ascii() {
+        let source = "/// The café is open.\n/// The function returns teh value.\nfn open() {}\n";
+
+        let findings = findings(source, &["teh"]);
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.matched_text, "teh");
+        assert_eq!(&source[finding.byte_range.clone()], "teh");
+        assert_eq!(
+            finding.start,
+            Position {
+                line: 2,
+                column: 26
+            }
+        );
+        assert_eq!(
+            finding.end,
+            Position {
+                line: 2,
+                column: 29
+            }
+        );
+        assert_eq!(
+            &finding.context.text[finding.context.offset..][..finding.context.length],
+            "teh"
+        );
+    }
+
+    #[test]
+    fn findings_across_lines() {
+        let source = "    /// Ünïcödé first\n    /// second line\n    fn open() {}\n";
+
+        let findings = findings(source, &["first\nsecond"]);
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.matched_text, "first\n    /// second");
+        assert_eq!(
+            finding.start,
+            Position {
+                line: 1,
+                column: 17
+            }
+        );
+        assert_eq!(
+            finding.end,
+            Position {
+                line: 2,
+                column: 15
+            }
+        );
+        assert_eq!(
+            finding.context.text,
+            "    /// Ünïcödé first\n    /// second line\n"
+        );
+    }
+
+    #[test]
+    fn findings_of_inner_docs() {
+        let source = "//! Ça marche.\n//! teh\n";
+
+        let findings = findings(source, &["teh"]);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].matched_text, "teh");
+        assert_eq!(findings[0].start, Position { line: 2, column: 5 });
+    }
+
+    /// Records the requests, and answers them without matches.
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<Request>>);
+
+    impl Checker for Recorder {
+        fn check(&self, request: &Request) -> Result<CheckResponse> {
+            self.0.borrow_mut().push(request.clone());
+            local_response("en-US", &[])
+        }
+
+        fn is_cacheable(&self) -> bool {
+            false
+        }
+    }
+
+    /// A cache which is not used, as the recorder is not cacheable.
+    struct NoCache;
+
+    impl Cacheable for NoCache {
+        fn new(_dir: impl AsRef<std::path::Path>) -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn get_hashed_key_raw(&self, _hashed_key: [u8; 32]) -> Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn set_hashed_key_raw(&self, _hashed_key: [u8; 32], _value: Vec<u8>) -> Result<()> {
+            Ok(())
+        }
+
+        fn hashed_key(_key: Vec<u8>) -> [u8; 32] {
+            [0; 32]
+        }
+    }
+
+    /// Returns the requests sent to check a doc.
+    fn requests(args: &[&str]) -> Vec<Request> {
+        let config = Config::parse_from([&["languagetool"], args].concat());
+        let checker = Recorder::default();
+
+        for mut doc in docs("/// Teh text.\nfn f() {}\n") {
+            doc.checked(&checker, &config, &NoCache).unwrap();
+            assert!(doc.check_response.is_some());
+        }
+
+        checker.0.into_inner()
+    }
+
+    #[test]
+    fn local_rules_are_not_sent() {
+        let requests = requests(&[
+            "--enable-rules",
+            "CODE_IDENTIFIER",
+            "--enable-rules",
+            "EN_QUOTES",
+            "--enable-categories",
+            "DOCS",
+            "--enable-categories",
+            "TYPOS",
+            "--disable-rules",
+            "GLOSSARY_GITHUB",
+            "--disable-categories",
+            "CODE",
+        ]);
+
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0].check_request;
+        assert_eq!(request.enabled_rules, Some(vec!["EN_QUOTES".to_owned()]));
+        assert_eq!(request.enabled_categories, Some(vec!["TYPOS".to_owned()]));
+        assert_eq!(request.disabled_rules, Some(vec![]));
+        assert_eq!(request.disabled_categories, Some(vec![]));
+    }
+
+    #[test]
+    fn only_local_rules() {
+        assert!(requests(&["--enable-only", "--enable-categories", "DOCS"]).is_empty());
+        assert_eq!(
+            requests(&["--enable-only", "--enable-rules", "EN_QUOTES"]).len(),
+            1
+        );
+    }
+
+    /// Returns the docs of a source, detected as German.
+    fn german_docs(source: &str, confidence: f64) -> Vec<Doc> {
+        let mut docs = docs(source);
+        for doc in &mut docs {
+            let mut response = local_response("en-US", &[]).unwrap();
+            response.language.detected_language.code = "de-DE".to_owned();
+            response.language.detected_language.name = "German (Germany)".to_owned();
+            response.language.detected_language.confidence = Some(confidence);
+            doc.check_response = Some(response);
+        }
+        docs
+    }
+
+    #[test]
+    fn language_mismatch() {
+        let source = "/// Größe der Straße.\nfn size() {}\n";
+
+        let docs = german_docs(source, 0.95);
+        assert_eq!(
+            docs[0]
+                .language_mismatch("en-US")
+                .map(|detected| detected.code.as_str()),
+            Some("de-DE")
+        );
+        assert!(docs[0].language_mismatch("de-AT").is_none());
+        assert!(docs[0].language_mismatch("auto").is_none());
+
+        let docs = german_docs(source, 0.5);
+        assert!(docs[0].language_mismatch("en-US").is_none());
+    }
+
+    #[test]
+    fn language_mismatch_finding() {
+        let source =
+            "impl Size {\n    /// Größe der Straße.\n    /// Über alles.\n    fn size() {}\n}\n";
+
+        let docs = german_docs(source, 0.95);
+        let finding = docs[0]
+            .language_mismatch_finding("lib.rs", source, "en-US", Severity::Warning)
+            .unwrap();
+
+        assert_eq!(finding.rule_id, LANGUAGE_MISMATCH_ID);
+        assert_eq!(finding.item_path, "Size::size");
+        assert_eq!(finding.matched_text, "de-DE");
+        assert_eq!(finding.severity, Severity::Warning);
+        assert_eq!(
+            &source[finding.byte_range.clone()],
+            "Größe der Straße.\n    /// Über alles."
+        );
+        assert_eq!(finding.start, Position { line: 2, column: 9 });
+        assert_eq!(
+            finding.end,
+            Position {
+                line: 3,
+                column: 20
+            }
+        );
+        assert!(docs[0]
+            .language_mismatch_finding("lib.rs", source, "de-DE", Severity::Warning)
+            .is_none());
+    }
+}
@@ -0,0 +1,296 @@
+//! The `finding` module contains the mistakes found in the docs, in terms of the source files.
+
+use core::fmt::Write;
+use core::ops::Range;
+
+use annotate_snippets::{Level, Renderer, Snippet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::utils::relative_path;
+
+/// A position in a source file. Lines and columns start at 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Returns the position of a byte offset in the source. Columns count chars.
+    #[must_use]
+    pub fn of_byte_offset(source: &str, byte_offset: usize) -> Self {
+        let before = source.get(..byte_offset).unwrap_or(source);
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            line: 1 + before.matches('\n').count(),
+            column: 1 + before[line_start..].chars().count(),
+        }
+    }
+}
+
+/// How serious a finding is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The name of the severity, as in `--deny`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    /// The default severity of a match, from the issue type and the category
+    /// of its rule.
+    #[must_use]
+    pub fn of_match(issue_type: &str, category_id: &str) -> Self {
+        match issue_type {
+            "misspelling" | "grammar" => Self::Error,
+            "typographical" | "whitespace" => Self::Note,
+            "style" | "register" | "duplication" | "inconsistency" => Self::Warning,
+            _ => match category_id {
+                "TYPOS" | "GRAMMAR" | "CONFUSED_WORDS" => Self::Error,
+                "TYPOGRAPHY" | "PUNCTUATION" | "CASING" => Self::Note,
+                _ => Self::Warning,
+            },
+        }
+    }
+
+    /// The level of the annotations.
+    #[must_use]
+    pub const fn level(self) -> Level {
+        match self {
+            Self::Note => Level::Note,
+            Self::Warning => Level::Warning,
+            Self::Error => Level::Error,
+        }
+    }
+}
+
+/// The source lines around a finding, to annotate it.
+#[derive(Debug, Clone, Default)]
+pub struct FindingContext {
+    /// The source lines, starting at the line of the finding.
+    pub text: String,
+    /// Byte offset of the finding in `text`.
+    pub offset: usize,
+    /// Length of the finding, in bytes.
+    pub length: usize,
+}
+
+/// A mistake found in the docs of a file.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub file: String,
+    /// Path of the documented item in the file, e.g. `Foo::bar`.
+    pub item_path: String,
+    pub rule_id: String,
+    pub category_id: String,
+    pub message: String,
+    /// Description of the rule.
+    pub description: String,
+    pub replacements: Vec<String>,
+    /// Links to explanations of the rule.
+    pub urls: Vec<String>,
+    pub matched_text: String,
+    pub start: Position,
+    pub end: Position,
+    /// Byte range of the finding in the source file.
+    pub byte_range: Range<usize>,
+    pub severity: Severity,
+    /// Whether the finding came from the cache.
+    pub cached: bool,
+    /// The sentence around the finding.
+    #[serde(skip)]
+    pub sentence: String,
+    #[serde(skip)]
+    pub context: FindingContext,
+}
+
+/// Identifies a finding, regardless of its line in the file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub file: String,
+    pub item_path: String,
+    pub rule_id: String,
+    pub matched_text: String,
+    /// Hash of the sentence around the finding.
+    pub context_hash: String,
+}
+
+impl Finding {
+    /// The path of the file, relative to the current directory and with `/`
+    /// separators.
+    ///
+    /// The files outside the current directory keep their absolute path.
+    #[must_use]
+    pub fn relative_file(&self) -> String {
+        relative_path(&self.file)
+    }
+
+    /// Returns the fingerprint of the finding.
+    #[must_use]
+    pub fn fingerprint(&self) -> Fingerprint {
+        let context_hash = Sha256::digest(self.sentence.as_bytes())
+            .iter()
+            .take(8)
+            .fold(String::new(), |mut hash, byte| {
+                write!(hash, "{byte:02x}").ok();
+                hash
+            });
+
+        Fingerprint {
+            file: self.relative_file(),
+            item_path: self.item_path.clone(),
+            rule_id: self.rule_id.clone(),
+            matched_text: self.matched_text.clone(),
+            context_hash,
+        }
+    }
+
+    /// Renders the finding as an annotated snippet of the source.
+    #[must_use]
+    pub fn annotate(&self, renderer: &Renderer) -> String {
+        let level = self.severity.level();
+        let span = self.context.offset..self.context.offset + self.context.length;
+
+        let replacements = self.replacements.join(", ");
+
+        let mut snippet = Snippet::source(&self.context.text)
+            .line_start(self.start.line)
+            .origin(&self.file)
+            .fold(true)
+            .annotation(level.span(span.clone()).label(&self.description));
+
+        if !replacements.is_empty() {
+            snippet = snippet.annotation(Level::Help.span(span).label(&replacements));
+        }
+
+        let message_id = format!("{}:{}", self.rule_id, self.category_id);
+
+        let message = level.title(&self.message).id(&message_id).snippet(snippet);
+
+        let annotation = renderer.render(message).to_string();
+        annotation
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Builds a finding of `matched_text` at the start of a line.
+    #[must_use]
+    pub fn finding(file: &str, rule_id: &str, category_id: &str, matched_text: &str) -> Finding {
+        Finding {
+            file: file.to_owned(),
+            item_path: "foo".to_owned(),
+            rule_id: rule_id.to_owned(),
+            category_id: category_id.to_owned(),
+            message: format!("{rule_id} message"),
+            description: format!("{rule_id} description"),
+            replacements: vec![],
+            urls: vec![],
+            matched_text: matched_text.to_owned(),
+            start: Position { line: 1, column: 5 },
+            end: Position {
+                line: 1,
+                column: 5 + matched_text.chars().count(),
+            },
+            byte_range: 4..4 + matched_text.len(),
+            severity: Severity::of_match("", category_id),
+            cached: false,
+            sentence: format!("/// {matched_text}"),
+            context: FindingContext {
+                text: format!("/// {matched_text}\n"),
+                offset: 4,
+                length: matched_text.len(),
+            },
+        }
+    }
+
+    #[test]
+    fn position_of_byte_offset() {
+        let source = "fn a() {}\n/// The café is open.\n/// teh\n";
+
+        assert_eq!(
+            Position::of_byte_offset(source, 0),
+            Position { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Position::of_byte_offset(source, 10),
+            Position { line: 2, column: 1 }
+        );
+
+        // `é` is two bytes, but one column.
+        let open = source.find("is open").unwrap();
+        assert_eq!(
+            Position::of_byte_offset(source, open),
+            Position {
+                line: 2,
+                column: 14
+            }
+        );
+
+        let teh = source.find("teh").unwrap();
+        assert_eq!(
+            Position::of_byte_offset(source, teh),
+            Position { line: 3, column: 5 }
+        );
+
+        assert_eq!(
+            Position::of_byte_offset(source, source.len()),
+            Position { line: 4, column: 1 }
+        );
+    }
+
+    #[test]
+    fn severity_of_match() {
+        assert_eq!(Severity::of_match("misspelling", "TYPOS"), Severity::Error);
+        assert_eq!(Severity::of_match("grammar", "MISC"), Severity::Error);
+        assert_eq!(Severity::of_match("whitespace", "TYPOS"), Severity::Note);
+        assert_eq!(Severity::of_match("style", "TYPOS"), Severity::Warning);
+        assert_eq!(
+            Severity::of_match("other", "CONFUSED_WORDS"),
+            Severity::Error
+        );
+        assert_eq!(Severity::of_match("other", "PUNCTUATION"), Severity::Note);
+        assert_eq!(Severity::of_match("other", "REDUNDANCY"), Severity::Warning);
+        assert_eq!(Severity::of_match("", "TYPOS"), Severity::Error);
+    }
+
+    #[test]
+    fn relative_file() {
+        let relative_file = |file: &str| finding(file, "RULE", "TYPOS", "teh").relative_file();
+
+        assert_eq!(relative_file("./src/lib.rs"), "src/lib.rs");
+
+        let absolute = std::env::current_dir().unwrap().join("src").join("lib.rs");
+        assert_eq!(relative_file(&absolute.to_string_lossy()), "src/lib.rs");
+
+        assert_eq!(relative_file("/elsewhere/lib.rs"), "/elsewhere/lib.rs");
+    }
+
+    #[test]
+    fn fingerprint_ignores_position() {
+        let first = finding("src/lib.rs", "RULE", "TYPOS", "teh");
+        let mut second = first.clone();
+        second.start.line += 10;
+        second.byte_range = 100..103;
+
+        assert_eq!(first.fingerprint(), second.fingerprint());
+
+        second.sentence = "/// teh other".to_owned();
+        assert_ne!(first.fingerprint(), second.fingerprint());
+    }
+}
@@ -0,0 +1,410 @@
+//! The `fix` module applies the replacements of the findings to the source files.
+//!
+//! Only the prose of `///` and `//!` comments is rewritten: replacements which span several
+//! lines, overlap the comment markers, or fall in inline code or code blocks are skipped.
+
+use std::collections::{BTreeMap, HashSet};
+
+use color_eyre::Result;
+use log::warn;
+use similar::TextDiff;
+
+use crate::finding::Finding;
+
+/// Categories whose replacements are applied by default.
+pub const DEFAULT_FIX_CATEGORIES: &[&str] = &["TYPOS", "PUNCTUATION", "CASING"];
+
+/// A replacement in a source file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edit {
+    /// Byte offset of the start of the replaced text.
+    pub start: usize,
+    /// Byte offset of the end of the replaced text.
+    pub end: usize,
+    /// The text matched by the finding, which must still be at the offsets.
+    pub original: String,
+    pub replacement: String,
+    pub rule_id: String,
+}
+
+/// A source file with the fixes applied.
+#[derive(Debug, Clone)]
+pub struct FixedFile {
+    pub file: String,
+    /// The path of the file in the diff, relative to the current directory.
+    pub relative_file: String,
+    pub original: String,
+    pub fixed: String,
+    /// Number of applied edits.
+    pub n_edits: usize,
+}
+
+/// Returns the length of the doc comment marker of a line, including the
+/// indentation, if the line is a `///` or `//!` comment.
+#[must_use]
+pub fn doc_comment_marker(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    (trimmed.starts_with("///") || trimmed.starts_with("//!"))
+        .then(|| line.len() - trimmed.len() + 3)
+}
+
+/// Returns the lines, starting at 0, which are in code blocks of doc comments.
+fn code_block_lines(source: &str) -> HashSet<usize> {
+    let mut lines = HashSet::new();
+    let mut in_code_block = false;
+
+    for (index, line) in source.lines().enumerate() {
+        let Some(marker) = doc_comment_marker(line) else {
+            in_code_block = false;
+            continue;
+        };
+
+        let text = line[marker..].trim_start();
+        if text.starts_with("```") || text.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            lines.insert(index);
+        } else if in_code_block {
+            lines.insert(index);
+        }
+    }
+
+    lines
+}
+
+/// Returns true if the edit only replaces prose of a doc comment.
+fn is_prose(source: &str, edit: &Edit, code_block_lines: &HashSet<usize>) -> bool {
+    let Some(replaced) = source.get(edit.start..edit.end) else {
+        return false;
+    };
+    if replaced.contains('\n') {
+        return false;
+    }
+
+    let line_start = source[..edit.start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let line_index = source[..line_start].matches('\n').count();
+    let line_end = source[line_start..]
+        .find('\n')
+        .map_or(source.len(), |index| line_start + index);
+    let line = &source[line_start..line_end];
+
+    let Some(marker) = doc_comment_marker(line) else {
+        return false;
+    };
+    let prose_start = line_start + marker;
+
+    // an odd number of backticks before the edit opens an inline code span.
+    edit.start >= prose_start
+        && !code_block_lines.contains(&line_index)
+        && source[prose_start..edit.start].matches('`').count() % 2 == 0
+}
+
+/// Applies the edits which only replace prose, and which do not overlap a
+/// previous edit, and returns the fixed source and the number of applied edits.
+///
+/// The edits whose offsets no longer hold the matched text, e.g. because the
+/// file changed since it was checked, are skipped with a warning.
+#[must_use]
+pub fn fix_source(source: &str, edits: &[Edit]) -> (String, usize) {
+    let code_block_lines = code_block_lines(source);
+
+    let mut edits: Vec<_> = edits
+        .iter()
+        .filter(|edit| {
+            let is_matched = source.get(edit.start..edit.end) == Some(edit.original.as_str());
+            if !is_matched {
+                warn!(
+                    "Skipping the fix of {} at bytes {}..{}: the source no longer holds {:?}.",
+                    edit.rule_id, edit.start, edit.end, edit.original
+                );
+            }
+            is_matched
+        })
+        .filter(|edit| is_prose(source, edit, &code_block_lines))
+        .collect();
+    edits.sort();
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut position = 0;
+    let mut n_edits = 0;
+
+    for edit in edits {
+        // overlapping edits are resolved in favor of the first one.
+        if edit.start < position {
+            continue;
+        }
+        fixed.push_str(&source[position..edit.start]);
+        fixed.push_str(&edit.replacement);
+        position = edit.end;
+        n_edits += 1;
+    }
+    fixed.push_str(&source[position..]);
+
+    (fixed, n_edits)
+}
+
+/// Returns the fixes as a unified diff, which applies with `git apply`.
+#[must_use]
+pub fn unified_diff(fixed_files: &[FixedFile]) -> String {
+    fixed_files
+        .iter()
+        .map(|fixed_file| {
+            // `git apply` takes the paths relative to the root of the repository.
+            let path = fixed_file.relative_file.trim_start_matches('/');
+
+            TextDiff::from_lines(&fixed_file.original, &fixed_file.fixed)
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{path}"), &format!("b/{path}"))
+                .to_string()
+        })
+        .collect()
+}
+
+/// The edits of a file.
+#[derive(Debug, Default)]
+struct FileFixes {
+    relative_file: String,
+    edits: Vec<Edit>,
+}
+
+/// Fixes of the findings, per file.
+#[derive(Debug, Default)]
+pub struct Fixes(BTreeMap<String, FileFixes>);
+
+impl Fixes {
+    /// Collects the first replacement of the findings of the categories.
+    #[must_use]
+    pub fn new(findings: &[Finding], categories: &[String]) -> Self {
+        let mut fixes: BTreeMap<String, FileFixes> = BTreeMap::new();
+
+        for finding in findings {
+            let Some(replacement) = finding.replacements.first() else {
+                continue;
+            };
+            if !categories.contains(&finding.category_id) {
+                continue;
+            }
+
+            let file_fixes = fixes
+                .entry(finding.file.clone())
+                .or_insert_with(|| FileFixes {
+                    relative_file: finding.relative_file(),
+                    edits: vec![],
+                });
+
+            file_fixes.edits.push(Edit {
+                start: finding.byte_range.start,
+                end: finding.byte_range.end,
+                original: finding.matched_text.clone(),
+                replacement: replacement.clone(),
+                rule_id: finding.rule_id.clone(),
+            });
+        }
+
+        Self(fixes)
+    }
+
+    /// Returns the files which are changed by the fixes.
+    ///
+    /// # Errors
+    /// If a file cannot be read.
+    pub fn fixed_files(&self) -> Result<Vec<FixedFile>> {
+        let mut fixed_files = vec![];
+
+        for (file, file_fixes) in &self.0 {
+            let original = std::fs::read_to_string(file)?;
+            let (fixed, n_edits) = fix_source(&original, &file_fixes.edits);
+            if n_edits > 0 && fixed != original {
+                fixed_files.push(FixedFile {
+                    file: file.clone(),
+                    relative_file: file_fixes.relative_file.clone(),
+                    original,
+                    fixed,
+                    n_edits,
+                });
+            }
+        }
+
+        Ok(fixed_files)
+    }
+
+    /// Writes the fixes to the files, and returns the changed files.
+    ///
+    /// # Errors
+    /// If a file cannot be read or written.
+    pub fn apply(&self) -> Result<Vec<FixedFile>> {
+        let fixed_files = self.fixed_files()?;
+
+        for fixed_file in &fixed_files {
+            std::fs::write(&fixed_file.file, &fixed_file.fixed)?;
+        }
+
+        Ok(fixed_files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the edit of the first occurrence of `original` after `after`.
+    fn edit(source: &str, after: &str, original: &str, replacement: &str) -> Edit {
+        let after = source.find(after).unwrap();
+        let start = after + source[after..].find(original).unwrap();
+        Edit {
+            start,
+            end: start + original.len(),
+            original: original.to_owned(),
+            replacement: replacement.to_owned(),
+            rule_id: "RULE".to_owned(),
+        }
+    }
+
+    #[test]
+    fn doc_comment_markers() {
+        assert_eq!(doc_comment_marker("/// Foo"), Some(3));
+        assert_eq!(doc_comment_marker("    //! Foo"), Some(7));
+        assert_eq!(doc_comment_marker("// Foo"), None);
+        assert_eq!(doc_comment_marker("let a = 1; /// Foo"), None);
+    }
+
+    #[test]
+    fn code_blocks() {
+        let source =
+            "/// Foo\n/// ```\n/// let a = 1;\n/// ```\n/// Bar\nfn foo() {}\n/// ~~~\n/// café\n";
+
+        let lines = code_block_lines(source);
+
+        assert_eq!(lines, HashSet::from([1, 2, 3, 6, 7]));
+    }
+
+    #[test]
+    fn code_blocks_end_with_the_doc_comment() {
+        let source = "/// ```\n/// let a = 1;\nfn foo() {}\n/// Bar\n";
+
+        let lines = code_block_lines(source);
+
+        assert_eq!(lines, HashSet::from([0, 1]));
+    }
+
+    #[test]
+    fn prose() {
+        let source = "/// Teh `teh` teh.\n/// ```\n/// teh\n/// ```\nfn teh() {}\n";
+        let lines = code_block_lines(source);
+
+        assert!(is_prose(source, &edit(source, "", "Teh", "The"), &lines));
+        assert!(is_prose(source, &edit(source, "` ", "teh", "the"), &lines));
+        // inline code
+        assert!(!is_prose(source, &edit(source, "`", "teh", "the"), &lines));
+        // code block
+        assert!(!is_prose(
+            source,
+            &edit(source, "```\n", "teh", "the"),
+            &lines
+        ));
+        // code
+        assert!(!is_prose(source, &edit(source, "fn", "teh", "the"), &lines));
+        // comment marker
+        assert!(!is_prose(
+            source,
+            &edit(source, "", "/// Teh", "The"),
+            &lines
+        ));
+        // several lines
+        assert!(!is_prose(
+            source,
+            &edit(source, "", "teh.\n///", "the"),
+            &lines
+        ));
+    }
+
+    #[test]
+    fn fix_after_non_ascii() {
+        let source = "/// The café is open.\n/// The function returns teh value.\nfn foo() {}\n";
+
+        let (fixed, n_edits) = fix_source(source, &[edit(source, "", "teh", "the")]);
+
+        assert_eq!(n_edits, 1);
+        assert_eq!(
+            fixed,
+            "/// The café is open.\n/// The function returns the value.\nfn foo() {}\n"
+        );
+    }
+
+    #[test]
+    fn fix_skips_shifted_edits() {
+        let source = "/// The café is open.\n/// The function returns teh value.\n";
+        let mut shifted = edit(source, "", "teh", "the");
+        shifted.start -= 1;
+        shifted.end -= 1;
+
+        let (fixed, n_edits) = fix_source(source, &[shifted]);
+
+        assert_eq!(n_edits, 0);
+        assert_eq!(fixed, source);
+    }
+
+    #[test]
+    fn diff_of_fixes() {
+        let original = "/// The café is open.\n/// The function returns teh value.\nfn foo() {}\n";
+        let (fixed, n_edits) = fix_source(original, &[edit(original, "", "teh", "the")]);
+
+        let diff = unified_diff(&[FixedFile {
+            file: "/home/user/crate/src/lib.rs".to_owned(),
+            relative_file: "src/lib.rs".to_owned(),
+            original: original.to_owned(),
+            fixed,
+            n_edits,
+        }]);
+
+        assert_eq!(
+            diff,
+            "--- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -1,3 +1,3 @@\n \
+             /// The café is open.\n\
+             -/// The function returns teh value.\n\
+             +/// The function returns the value.\n \
+             fn foo() {}\n"
+        );
+    }
+
+    #[test]
+    fn diff_of_absolute_paths() {
+        let diff = unified_diff(&[FixedFile {
+            file: "/tmp/lib.rs".to_owned(),
+            relative_file: "/tmp/lib.rs".to_owned(),
+            original: "/// teh\n".to_owned(),
+            fixed: "/// the\n".to_owned(),
+            n_edits: 1,
+        }]);
+
+        assert!(diff.starts_with("--- a/tmp/lib.rs\n+++ b/tmp/lib.rs\n"));
+    }
+
+    #[test]
+    fn fix_keeps_the_first_of_overlapping_edits() {
+        let source = "/// Teh teh.\n";
+        let first = edit(source, "", "Teh teh", "The");
+        let second = edit(source, " ", "teh", "the");
+
+        let (fixed, n_edits) = fix_source(source, &[second, first]);
+
+        assert_eq!(n_edits, 1);
+        assert_eq!(fixed, "/// The.\n");
+    }
+
+    #[test]
+    fn fix_several_edits() {
+        let source = "/// Teh über teh.\n";
+        let first = edit(source, "", "Teh", "The");
+        let second = edit(source, "über", "teh", "the");
+
+        let (fixed, n_edits) = fix_source(source, &[first, second]);
+
+        assert_eq!(n_edits, 2);
+        assert_eq!(fixed, "/// The über the.\n");
+    }
+}
@@ -0,0 +1,127 @@
+//! The `gate` module decides whether the findings fail the run, e.g. in CI.
+//!
+//! The run fails if a finding is denied, i.e. its rule, category or severity is listed with
+//! `--deny`, or if the number of findings exceeds a configured maximum.
+
+use std::collections::BTreeMap;
+
+use crate::cli::Config;
+use crate::finding::Finding;
+
+/// Returns the reasons why the findings fail the run, if any.
+#[must_use]
+pub fn failures(config: &Config, findings: &[Finding]) -> Vec<String> {
+    let mut failures = vec![];
+
+    let denied = findings
+        .iter()
+        .filter(|finding| config.is_denied(finding))
+        .count();
+    if denied > 0 {
+        failures.push(format!("{denied} findings are denied"));
+    }
+
+    if let Some(max_findings) = config.max_findings {
+        if findings.len() > max_findings {
+            failures.push(format!(
+                "{} findings exceed the maximum of {max_findings}",
+                findings.len()
+            ));
+        }
+    }
+
+    let mut category_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for finding in findings {
+        *category_counts.entry(&finding.category_id).or_default() += 1;
+    }
+
+    for (category, max_findings) in &config.max_category_findings {
+        let count = category_counts
+            .get(category.as_str())
+            .copied()
+            .unwrap_or_default();
+        if count > *max_findings {
+            failures.push(format!(
+                "{count} {category} findings exceed the maximum of {max_findings}"
+            ));
+        }
+    }
+
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::finding::tests::finding;
+
+    fn config(args: &[&str]) -> Config {
+        Config::parse_from(core::iter::once("languagetool").chain(args.iter().copied()))
+    }
+
+    fn findings() -> Vec<Finding> {
+        vec![
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "wierd"),
+            finding(
+                "src/lib.rs",
+                "COMMA_PARENTHESIS_WHITESPACE",
+                "TYPOGRAPHY",
+                " ,",
+            ),
+        ]
+    }
+
+    #[test]
+    fn no_failures_by_default() {
+        assert!(failures(&config(&[]), &findings()).is_empty());
+    }
+
+    #[test]
+    fn denied_findings() {
+        let denied = |args: &[&str]| {
+            findings()
+                .iter()
+                .filter(|finding| config(args).is_denied(finding))
+                .count()
+        };
+
+        assert_eq!(denied(&[]), 0);
+        assert_eq!(denied(&["--deny", "TYPOS"]), 2);
+        assert_eq!(denied(&["--deny", "COMMA_PARENTHESIS_WHITESPACE"]), 1);
+        assert_eq!(denied(&["--deny", "error"]), 2);
+        assert_eq!(denied(&["--deny", "note", "--deny", "error"]), 3);
+        assert_eq!(denied(&["--deny", "warning"]), 0);
+
+        assert_eq!(
+            failures(&config(&["--deny", "error"]), &findings()),
+            vec!["2 findings are denied"]
+        );
+    }
+
+    #[test]
+    fn max_findings() {
+        assert!(failures(&config(&["--max-findings", "3"]), &findings()).is_empty());
+        assert_eq!(
+            failures(&config(&["--max-findings", "2"]), &findings()),
+            vec!["3 findings exceed the maximum of 2"]
+        );
+    }
+
+    #[test]
+    fn max_category_findings() {
+        let mut config = config(&[]);
+        config.max_category_findings = BTreeMap::from([
+            ("TYPOS".to_owned(), 1),
+            ("TYPOGRAPHY".to_owned(), 1),
+            ("GRAMMAR".to_owned(), 0),
+        ]);
+
+        assert_eq!(
+            failures(&config, &findings()),
+            vec!["2 TYPOS findings exceed the maximum of 1"]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! The `interactive` module walks through the findings in the terminal, and applies the chosen
+//! fixes to the source files as the session goes.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use annotate_snippets::Renderer;
+use color_eyre::Result;
+
+use crate::dictionary::Dictionary;
+use crate::finding::Finding;
+use crate::fix::{doc_comment_marker, fix_source, Edit};
+
+/// What to do with a finding.
+enum Action {
+    Replace(String),
+    Skip,
+    /// Allow the rule for the documented item.
+    Allow,
+    /// Add the word to the project dictionary.
+    AddWord,
+    Quit,
+}
+
+/// The edits made to a file during the session, to locate the next findings.
+#[derive(Debug, Default)]
+struct FileEdits {
+    /// Number of bytes added before the next findings.
+    added: usize,
+    /// Number of bytes removed before the next findings.
+    removed: usize,
+    /// End of the last replaced text, in the original file.
+    end: usize,
+}
+
+impl FileEdits {
+    /// The offset in the edited file of an offset in the original file, after
+    /// the edits.
+    const fn offset(&self, original_offset: usize) -> usize {
+        original_offset + self.added - self.removed
+    }
+
+    /// Records a replacement of the text which ends at `original_end` in the
+    /// original file.
+    fn replaced(&mut self, edit: &Edit, original_end: usize) {
+        self.added += edit.replacement.len();
+        self.removed += edit.end - edit.start;
+        self.end = original_end;
+    }
+
+    /// Records an insertion before the next findings.
+    fn inserted(&mut self, text: &str) {
+        self.added += text.len();
+    }
+}
+
+/// Returns the offset of the first line of the doc block of the line at
+/// `offset`, and the line of the directive which allows a rule for the
+/// documented item, if the line is a `///` or `//!` comment.
+fn allow_directive(source: &str, offset: usize, rule_id: &str) -> Option<(usize, String)> {
+    let mut line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = source[line_start..].lines().next().unwrap_or_default();
+    let marker = doc_comment_marker(line)?;
+    let kind = &line[marker - 3..marker];
+
+    // the directive goes before the first line of the block, so that it does
+    // not split a paragraph.
+    while line_start > 0 {
+        let previous_start = source[..line_start - 1]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let previous = &source[previous_start..line_start - 1];
+        match doc_comment_marker(previous) {
+            Some(previous_marker) if &previous[previous_marker - 3..previous_marker] == kind => {
+                line_start = previous_start;
+            }
+            _ => break,
+        }
+    }
+
+    let first_line = source[line_start..].lines().next().unwrap_or_default();
+    let first_marker = doc_comment_marker(first_line)?;
+
+    Some((
+        line_start,
+        format!(
+            "{} <!-- languagetool::allow({rule_id}) -->\n",
+            &first_line[..first_marker]
+        ),
+    ))
+}
+
+/// Reads a line of the input, without the line break, or `None` at the end of
+/// the input.
+fn read_line(input: &mut impl BufRead) -> Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim_end_matches(['\n', '\r']).to_owned()))
+}
+
+/// Asks what to do with a finding.
+fn prompt(
+    finding: &Finding,
+    can_add_word: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Action> {
+    loop {
+        for (index, replacement) in finding.replacements.iter().enumerate() {
+            write!(output, "[{}] {replacement}  ", index + 1)?;
+        }
+        if !finding.replacements.is_empty() {
+            writeln!(output)?;
+        }
+
+        let item = if finding.item_path.is_empty() {
+            "the crate"
+        } else {
+            &finding.item_path
+        };
+        write!(
+            output,
+            "[c] custom replacement, [s] skip, [i] ignore {} for {item}",
+            finding.rule_id
+        )?;
+        if can_add_word {
+            write!(
+                output,
+                ", [a] add {:?} to the dictionary",
+                finding.matched_text
+            )?;
+        }
+        write!(output, ", [q] quit: ")?;
+        output.flush()?;
+
+        let Some(answer) = read_line(input)? else {
+            return Ok(Action::Quit);
+        };
+
+        match answer.trim() {
+            "" | "s" => return Ok(Action::Skip),
+            "i" => return Ok(Action::Allow),
+            "a" if can_add_word => return Ok(Action::AddWord),
+            "q" => return Ok(Action::Quit),
+            "c" => {
+                write!(output, "Replacement: ")?;
+                output.flush()?;
+                return Ok(read_line(input)?.map_or(Action::Quit, Action::Replace));
+            }
+            answer => {
+                if let Some(replacement) = answer
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| number.checked_sub(1))
+                    .and_then(|index| finding.replacements.get(index))
+                {
+                    return Ok(Action::Replace(replacement.clone()));
+                }
+                writeln!(output, "Unknown choice: {answer}")?;
+            }
+        }
+    }
+}
+
+/// Walks through the findings, and applies the chosen fixes to the files.
+///
+/// Words added to the dictionary are appended to the `dictionary` file.
+///
+/// # Errors
+/// If the terminal or a file cannot be read or written.
+pub fn run(findings: &[Finding], dictionary: &Path) -> Result<()> {
+    let renderer = Renderer::styled();
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+
+    let mut findings: Vec<_> = findings.iter().collect();
+    findings.sort_by(|first, second| {
+        (&first.file, first.byte_range.start).cmp(&(&second.file, second.byte_range.start))
+    });
+
+    let mut file_edits: HashMap<&str, FileEdits> = HashMap::new();
+    let mut allowed: HashSet<(&str, &str, &str)> = HashSet::new();
+    let mut added_words: HashSet<&str> = HashSet::new();
+    let mut n_edits = 0;
+
+    for finding in findings {
+        let rule_key = (
+            finding.file.as_str(),
+            finding.item_path.as_str(),
+            finding.rule_id.as_str(),
+        );
+        let can_add_word = Dictionary::is_spelling(&finding.category_id);
+
+        if allowed.contains(&rule_key)
+            || (can_add_word && added_words.contains(finding.matched_text.as_str()))
+        {
+            continue;
+        }
+
+        let edits = file_edits.entry(&finding.file).or_default();
+
+        // the text of the finding was already replaced.
+        if finding.byte_range.start < edits.end {
+            continue;
+        }
+
+        writeln!(output, "{}", finding.annotate(&renderer))?;
+
+        let start = edits.offset(finding.byte_range.start);
+        let end = edits.offset(finding.byte_range.end);
+
+        match prompt(finding, can_add_word, &mut input, &mut output)? {
+            Action::Replace(replacement) => {
+                let source = std::fs::read_to_string(&finding.file)?;
+                if source.get(start..end) != Some(finding.matched_text.as_str()) {
+                    writeln!(
+                        output,
+                        "The file changed since it was checked, the text is left as is."
+                    )?;
+                    continue;
+                }
+
+                let edit = Edit {
+                    start,
+                    end,
+                    original: finding.matched_text.clone(),
+                    replacement,
+                    rule_id: finding.rule_id.clone(),
+                };
+                let (fixed, n_applied) = fix_source(&source, core::slice::from_ref(&edit));
+                if n_applied == 0 {
+                    writeln!(
+                        output,
+                        "The text is not prose of a `///` or `//!` comment, it is left as is."
+                    )?;
+                    continue;
+                }
+                std::fs::write(&finding.file, fixed)?;
+
+                edits.replaced(&edit, finding.byte_range.end);
+                n_edits += 1;
+            }
+            Action::Allow => {
+                let mut source = std::fs::read_to_string(&finding.file)?;
+                let Some((line_start, directive)) =
+                    allow_directive(&source, start, &finding.rule_id)
+                else {
+                    writeln!(
+                        output,
+                        "The text is not in a `///` or `//!` comment, the rule cannot be allowed here."
+                    )?;
+                    continue;
+                };
+
+                source.insert_str(line_start, &directive);
+                std::fs::write(&finding.file, source)?;
+
+                edits.inserted(&directive);
+                allowed.insert(rule_key);
+                n_edits += 1;
+            }
+            Action::AddWord => {
+                Dictionary::add(dictionary, &finding.matched_text)?;
+                added_words.insert(&finding.matched_text);
+            }
+            Action::Skip => {}
+            Action::Quit => break,
+        }
+    }
+
+    writeln!(output, "Applied {n_edits} edits.")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_after_edits() {
+        let mut edits = FileEdits::default();
+        assert_eq!(edits.offset(10), 10);
+
+        // `teh` at 4..7 becomes `the word`.
+        let edit = Edit {
+            start: 4,
+            end: 7,
+            original: "teh".to_owned(),
+            replacement: "the word".to_owned(),
+            rule_id: "RULE".to_owned(),
+        };
+        edits.replaced(&edit, 7);
+        assert_eq!(edits.offset(10), 15);
+        assert_eq!(edits.end, 7);
+
+        // `a lot` at 20..25, shifted to 25..30, becomes `much`.
+        let edit = Edit {
+            start: 25,
+            end: 30,
+            original: "a lot".to_owned(),
+            replacement: "much".to_owned(),
+            rule_id: "RULE".to_owned(),
+        };
+        edits.replaced(&edit, 25);
+        assert_eq!(edits.offset(30), 34);
+
+        edits.inserted("/// <!-- languagetool::allow(RULE) -->\n");
+        assert_eq!(edits.offset(30), 73);
+    }
+
+    #[test]
+    fn offsets_locate_the_next_findings() {
+        let original = "/// Teh café is teh best.\n";
+        let mut source = original.to_owned();
+        let mut edits = FileEdits::default();
+
+        for (word, replacement) in [("Teh", "The"), ("teh", "the very")] {
+            let original_start = original.find(word).unwrap();
+            let start = edits.offset(original_start);
+            let end = edits.offset(original_start + word.len());
+            assert_eq!(&source[start..end], word);
+
+            let edit = Edit {
+                start,
+                end,
+                original: word.to_owned(),
+                replacement: replacement.to_owned(),
+                rule_id: "RULE".to_owned(),
+            };
+            source.replace_range(start..end, replacement);
+            edits.replaced(&edit, original_start + word.len());
+        }
+
+        assert_eq!(source, "/// The café is the very best.\n");
+    }
+
+    #[test]
+    fn allow_directive_at_the_top_of_the_doc_block() {
+        let source = "/// Foo.\nfn foo() {}\n\n    /// First line\n    /// of teh paragraph.\n    fn bar() {}\n";
+        let offset = source.find("teh").unwrap();
+
+        let (line_start, directive) = allow_directive(source, offset, "RULE").unwrap();
+
+        assert_eq!(line_start, source.find("    /// First").unwrap());
+        assert_eq!(directive, "    /// <!-- languagetool::allow(RULE) -->\n");
+    }
+
+    #[test]
+    fn allow_directive_in_inner_docs() {
+        let source = "//! Teh crate.\n\n/// Foo.\nfn foo() {}\n";
+
+        let (line_start, directive) = allow_directive(source, 4, "RULE").unwrap();
+
+        assert_eq!(line_start, 0);
+        assert_eq!(directive, "//! <!-- languagetool::allow(RULE) -->\n");
+    }
+
+    #[test]
+    fn allow_directive_stops_at_other_comments() {
+        let source = "//! Crate.\n/// First.\n/// teh\n";
+        let offset = source.find("teh").unwrap();
+
+        let (line_start, _) = allow_directive(source, offset, "RULE").unwrap();
+
+        assert_eq!(line_start, source.find("/// First").unwrap());
+    }
+
+    #[test]
+    fn no_allow_directive_outside_doc_comments() {
+        let source = "// teh\nfn foo() {}\n";
+
+        assert!(allow_directive(source, 3, "RULE").is_none());
+    }
+}
@@ -28,6 +28,21 @@ pub enum Categories {
     AmericanEnglishStyle,
     NonstandardPhrases,
     Collocations,
+    /// Code in the prose of the docs, checked locally.
+    Code,
+    /// Terms of the glossary, checked locally.
+    Terminology,
+    /// Conventions of the Rust docs, checked locally.
+    Docs,
+}
+
+impl Categories {
+    /// Returns true if the category is checked locally, and unknown to
+    /// `languagetool`.
+    #[must_use]
+    pub const fn is_local(&self) -> bool {
+        matches!(self, Self::Code | Self::Terminology | Self::Docs)
+    }
 }
 
 impl core::fmt::Display for Categories {
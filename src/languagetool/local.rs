@@ -0,0 +1,92 @@
+use color_eyre::Result;
+use languagetool_rust::check::Match;
+use languagetool_rust::CheckResponse;
+use serde_json::json;
+
+/// A match found without asking a `languagetool` server.
+///
+/// The `languagetool_rust` types are not constructible outside of their crate,
+/// so the match is built from the same JSON a server would respond with.
+#[derive(Debug, Clone)]
+pub struct LocalMatch {
+    pub offset: usize,
+    pub length: usize,
+    pub message: String,
+    pub short_message: String,
+    pub replacements: Vec<String>,
+    pub rule_id: String,
+    pub rule_description: String,
+    pub issue_type: String,
+    pub category_id: String,
+    pub category_name: String,
+}
+
+impl LocalMatch {
+    /// Converts into a `languagetool` match over `text`.
+    ///
+    /// # Errors
+    /// If the match cannot be deserialized.
+    pub fn into_match(self, text: &str) -> Result<Match> {
+        let replacements: Vec<_> = self
+            .replacements
+            .into_iter()
+            .map(|value| json!({ "value": value }))
+            .collect();
+
+        Ok(serde_json::from_value(json!({
+            "context": {
+                "text": text,
+                "offset": self.offset,
+                "length": self.length,
+            },
+            "contextForSureMatch": 0,
+            "ignoreForIncompleteSentence": false,
+            "offset": self.offset,
+            "length": self.length,
+            "message": self.message,
+            "shortMessage": self.short_message,
+            "replacements": replacements,
+            "rule": {
+                "id": self.rule_id,
+                "description": self.rule_description,
+                "issueType": self.issue_type,
+                "category": {
+                    "id": self.category_id,
+                    "name": self.category_name,
+                },
+                "isPremium": false,
+                "urls": [],
+            },
+            "sentence": text,
+            "type": { "typeName": "Other" },
+        }))?)
+    }
+}
+
+/// Builds a `languagetool` response from locally found matches.
+///
+/// # Errors
+/// If the response cannot be deserialized.
+pub fn local_response(language: &str, matches: &[Match]) -> Result<CheckResponse> {
+    Ok(serde_json::from_value(json!({
+        "language": {
+            "code": language,
+            "name": language,
+            "detectedLanguage": {
+                "code": language,
+                "name": language,
+                "confidence": 1.0,
+            },
+        },
+        "matches": matches,
+        "software": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "buildDate": "",
+            "apiVersion": 1,
+            "premium": false,
+            "status": "",
+        },
+        "warnings": { "incompleteResults": false },
+    }))?)
+}
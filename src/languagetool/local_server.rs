@@ -0,0 +1,242 @@
+use core::net::Ipv4Addr;
+use core::time::Duration;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Instant;
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+/// How long to wait for a spawned server to accept connections.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often to poll a spawned server while waiting for it.
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait for the answer of a server to a probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The file, in the state directory, which records a reusable server.
+const STATE_FILE: &str = "local-server.json";
+
+/// A running server, as recorded for reuse across invocations.
+#[derive(Debug, Serialize, Deserialize)]
+struct ServerState {
+    jar: PathBuf,
+    port: u16,
+    pid: u32,
+}
+
+/// A `languagetool` server process on the local machine.
+///
+/// The server is shut down when this is dropped, unless it is kept for reuse.
+pub struct LocalServer {
+    child: Option<Child>,
+    port: u16,
+}
+
+impl LocalServer {
+    /// Hostname of the local server.
+    pub const HOSTNAME: &'static str = "http://127.0.0.1";
+
+    /// Spawns a server from `languagetool-server.jar` on a free port and waits
+    /// until it is ready.
+    ///
+    /// With `reuse`, a server previously kept running in `state_dir` is used if
+    /// it still responds, and a newly spawned server is kept running after
+    /// this is dropped.
+    ///
+    /// # Errors
+    /// If the server cannot be spawned or does not become ready.
+    pub fn start(jar: &Path, reuse: bool, state_dir: &Path) -> Result<Self> {
+        let state_file = state_dir.join(STATE_FILE);
+
+        if reuse {
+            if let Some(port) = Self::reusable_port(jar, &state_file) {
+                info!("Reusing languagetool server on port {port}.");
+                return Ok(Self { child: None, port });
+            }
+        }
+
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?
+            .local_addr()?
+            .port();
+
+        info!("Starting languagetool server on port {port}.");
+
+        let java = std::env::var_os("JAVA_HOME").map_or_else(
+            || PathBuf::from("java"),
+            |home| Path::new(&home).join("bin/java"),
+        );
+
+        let mut child = Command::new(java)
+            .arg("-cp")
+            .arg(jar)
+            .arg("org.languagetool.server.HTTPServer")
+            .arg("--port")
+            .arg(port.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        if let Err(error) = Self::wait_until_ready(&mut child, port) {
+            child.kill().ok();
+            child.wait().ok();
+            return Err(error);
+        }
+
+        if reuse {
+            std::fs::create_dir_all(state_dir)?;
+            let state = ServerState {
+                jar: jar.to_path_buf(),
+                port,
+                pid: child.id(),
+            };
+            std::fs::write(&state_file, serde_json::to_vec(&state)?)?;
+            return Ok(Self { child: None, port });
+        }
+
+        Ok(Self {
+            child: Some(child),
+            port,
+        })
+    }
+
+    /// Port of the local server.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the port of the recorded server, if it was spawned from the same
+    /// jar and still answers as a `languagetool` server, rather than another
+    /// process which took the port since.
+    fn reusable_port(jar: &Path, state_file: &Path) -> Option<u16> {
+        let state: ServerState = serde_json::from_slice(&std::fs::read(state_file).ok()?).ok()?;
+
+        debug!("Found recorded languagetool server: {state:?}");
+
+        (state.jar == jar && Self::responds(state.port)).then_some(state.port)
+    }
+
+    /// Returns true if a `languagetool` server answers on the port, i.e. lists
+    /// its languages.
+    fn responds(port: u16) -> bool {
+        let probe = || -> std::io::Result<bool> {
+            let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+            stream.set_read_timeout(Some(PROBE_TIMEOUT))?;
+            stream.set_write_timeout(Some(PROBE_TIMEOUT))?;
+            stream.write_all(b"GET /v2/languages HTTP/1.0\r\nHost: 127.0.0.1\r\n\r\n")?;
+
+            let mut response = vec![];
+            stream.read_to_end(&mut response)?;
+            Ok(is_languages_response(&String::from_utf8_lossy(&response)))
+        };
+
+        probe().unwrap_or(false)
+    }
+
+    /// Waits until the spawned server answers.
+    fn wait_until_ready(child: &mut Child, port: u16) -> Result<()> {
+        let start = Instant::now();
+
+        while start.elapsed() < STARTUP_TIMEOUT {
+            if let Some(status) = child.try_wait()? {
+                bail!("languagetool server exited during startup: {status}");
+            }
+
+            if Self::responds(port) {
+                debug!("languagetool server is ready after {:?}", start.elapsed());
+                return Ok(());
+            }
+
+            std::thread::sleep(STARTUP_POLL_INTERVAL);
+        }
+
+        bail!(
+            "languagetool server is not ready after {}s",
+            STARTUP_TIMEOUT.as_secs()
+        )
+    }
+}
+
+/// Returns true if an HTTP response is a successful list of languages.
+fn is_languages_response(response: &str) -> bool {
+    let Some((head, body)) = response.split_once("\r\n\r\n") else {
+        return false;
+    };
+
+    head.split_whitespace().nth(1) == Some("200")
+        && serde_json::from_str::<Vec<serde_json::Value>>(body).is_ok_and(|languages| {
+            languages
+                .iter()
+                .any(|language| language.get("longCode").is_some())
+        })
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            info!("Stopping languagetool server on port {}.", self.port);
+            child.kill().ok();
+            child.wait().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_response() {
+        assert!(is_languages_response(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n[{\"name\":\"English (US)\",\"code\":\"en\",\"longCode\":\"en-US\"}]"
+        ));
+        assert!(!is_languages_response(
+            "HTTP/1.1 404 Not Found\r\n\r\n[{\"longCode\":\"en-US\"}]"
+        ));
+        assert!(!is_languages_response(
+            "HTTP/1.1 200 OK\r\n\r\n<html>another server</html>"
+        ));
+        assert!(!is_languages_response("SSH-2.0-OpenSSH_9.6\r\n"));
+    }
+
+    #[test]
+    fn no_reusable_port_without_server() {
+        let state_dir =
+            std::env::temp_dir().join(format!("languagetool-test-{}", std::process::id()));
+        std::fs::create_dir_all(&state_dir).unwrap();
+        let state_file = state_dir.join(STATE_FILE);
+
+        // a port which accepts connections, but is not a languagetool server.
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let jar = Path::new("languagetool-server.jar");
+        let state = ServerState {
+            jar: jar.to_path_buf(),
+            port,
+            pid: 0,
+        };
+        std::fs::write(&state_file, serde_json::to_vec(&state).unwrap()).unwrap();
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"HTTP/1.0 200 OK\r\n\r\nhello").unwrap();
+        });
+
+        assert_eq!(LocalServer::reusable_port(jar, &state_file), None);
+        handle.join().unwrap();
+        assert_eq!(
+            LocalServer::reusable_port(Path::new("other.jar"), &state_file),
+            None
+        );
+
+        std::fs::remove_dir_all(&state_dir).unwrap();
+    }
+}
@@ -1 +1,3 @@
 pub mod categories;
+pub mod local;
+pub mod local_server;
@@ -1,7 +1,20 @@
 //! This main module for cargo grammar checking. Use wisely.
 
+pub mod baseline;
 pub mod cache;
+pub mod checker;
 pub mod cli;
+pub mod config;
+pub mod dictionary;
 pub mod doc;
+pub mod finding;
+pub mod fix;
+pub mod gate;
+pub mod interactive;
 pub mod languagetool;
+pub mod markdown;
+pub mod report;
+pub mod rules;
+pub mod suppression;
 pub mod utils;
+pub mod visitor;
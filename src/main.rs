@@ -1,10 +1,20 @@
+use std::process::ExitCode;
+
+use cargo_languagetool::cli::{Cargo, ERROR_EXIT_CODE};
 use clap::Parser;
 use color_eyre::Result;
 
-fn main() -> Result<()> {
+fn run() -> Result<ExitCode> {
     color_eyre::install()?;
     dotenv::dotenv().ok();
     env_logger::init();
 
-    cargo_languagetool::cli::Cargo::parse().run()
+    Cargo::parse().run()
+}
+
+fn main() -> ExitCode {
+    run().unwrap_or_else(|error| {
+        eprintln!("Error: {error:?}");
+        ExitCode::from(ERROR_EXIT_CODE)
+    })
 }
@@ -0,0 +1,104 @@
+//! The `markdown` module splits the prose of the docs from their code, for the checks which are
+//! done locally.
+
+/// Returns, for each char of a markdown text, whether it is code: in a code
+/// block, in inline code, or a backtick.
+#[must_use]
+pub fn code_chars(text: &str) -> Vec<bool> {
+    let mut code = Vec::with_capacity(text.len());
+    let mut in_code_block = false;
+
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            // the newline
+            code.push(in_code_block);
+        }
+
+        let trimmed_line = line.trim_start();
+
+        if trimmed_line.starts_with("```") || trimmed_line.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            code.extend(line.chars().map(|_| true));
+        } else if in_code_block {
+            code.extend(line.chars().map(|_| true));
+        } else {
+            let mut in_code = false;
+            for chr in line.chars() {
+                if chr == '`' {
+                    in_code = !in_code;
+                    code.push(true);
+                } else {
+                    code.push(in_code);
+                }
+            }
+        }
+    }
+
+    code
+}
+
+/// Splits the prose of a markdown text at whitespace and at the `separators`.
+///
+/// Code blocks and the tokens with inline code are skipped. Returns the char
+/// offset of each token.
+#[must_use]
+pub fn prose_tokens<'a>(text: &'a str, separators: &str) -> Vec<(usize, &'a str)> {
+    let code = code_chars(text);
+    let mut tokens = vec![];
+    // byte index, char index, and whether the token has code
+    let mut token_start: Option<(usize, usize, bool)> = None;
+
+    for (char_index, (byte_index, chr)) in text
+        .char_indices()
+        .chain(core::iter::once((text.len(), ' ')))
+        .enumerate()
+    {
+        let is_code = code.get(char_index).copied().unwrap_or_default();
+
+        if (chr.is_whitespace() || separators.contains(chr)) && !is_code {
+            if let Some((start_byte, start_char, has_code)) = token_start.take() {
+                if !has_code {
+                    tokens.push((start_char, &text[start_byte..byte_index]));
+                }
+            }
+            continue;
+        }
+
+        let token = token_start.get_or_insert((byte_index, char_index, false));
+        token.2 |= is_code;
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code() {
+        let text = "a `b` c\n```\nd\n```\né";
+
+        let code: String = text
+            .chars()
+            .zip(code_chars(text))
+            .map(|(chr, is_code)| if is_code { '#' } else { chr })
+            .collect();
+
+        assert_eq!(code, "a ### c\n#########\né");
+    }
+
+    #[test]
+    fn tokens() {
+        let text = "Héllo `a b` wörld(s), x`y`\n```\ncode\n```\nend.";
+
+        assert_eq!(
+            prose_tokens(text, ""),
+            vec![(0, "Héllo"), (12, "wörld(s),"), (40, "end.")]
+        );
+        assert_eq!(
+            prose_tokens(text, "(),"),
+            vec![(0, "Héllo"), (12, "wörld"), (18, "s"), (40, "end.")]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::xml::escape;
+use super::Report;
+use crate::finding::{Finding, Severity};
+
+/// The Checkstyle severity of a severity.
+const fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Note => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Writes the findings as a Checkstyle XML report.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let mut files: BTreeMap<String, Vec<&Finding>> = BTreeMap::new();
+    for finding in report.findings {
+        files
+            .entry(finding.relative_file())
+            .or_default()
+            .push(finding);
+    }
+
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(output, r#"<checkstyle version="4.3">"#)?;
+
+    for (file, findings) in &files {
+        writeln!(output, r#"  <file name="{}">"#, escape(file))?;
+        for finding in findings {
+            writeln!(
+                output,
+                r#"    <error line="{}" column="{}" severity="{}" message="{}" source="{}"/>"#,
+                finding.start.line,
+                finding.start.column,
+                severity(finding.severity),
+                escape(&finding.message),
+                escape(&finding.rule_id)
+            )?;
+        }
+        writeln!(output, "  </file>")?;
+    }
+
+    writeln!(output, "</checkstyle>")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn files() {
+        let mut quotes = finding("src/main.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        quotes.message = "Use \"smart\" quotes & <marks>.".to_owned();
+        let findings = [
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            quotes,
+        ];
+
+        assert_eq!(
+            written(Format::Checkstyle, &findings, None),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="src/lib.rs">
+    <error line="1" column="5" severity="error" message="MORFOLOGIK_RULE_EN_US message" source="MORFOLOGIK_RULE_EN_US"/>
+  </file>
+  <file name="src/main.rs">
+    <error line="1" column="5" severity="info" message="Use &quot;smart&quot; quotes &amp; &lt;marks&gt;." source="EN_QUOTES"/>
+  </file>
+</checkstyle>
+"#
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::Report;
+use crate::baseline::{stale_entry_message, STALE_BASELINE_ID};
+use crate::finding::Severity;
+
+/// The workflow command of a severity.
+const fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Note => "notice",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property of a workflow command.
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Writes the findings as GitHub Actions workflow commands, which annotate
+/// the files of the pull request.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    for entry in report.stale_entries {
+        writeln!(
+            output,
+            "::warning file={},title={}::{}",
+            escape_property(&entry.file),
+            STALE_BASELINE_ID,
+            escape_data(&stale_entry_message(entry))
+        )?;
+    }
+
+    for finding in report.findings {
+        let mut message = finding.message.clone();
+        if !finding.replacements.is_empty() {
+            message.push_str("\nSuggestions: ");
+            message.push_str(&finding.replacements.join(", "));
+        }
+
+        writeln!(
+            output,
+            "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+            command(finding.severity),
+            escape_property(&finding.relative_file()),
+            finding.start.line,
+            finding.start.column,
+            finding.end.line,
+            finding.end.column,
+            escape_property(&finding.rule_id),
+            escape_data(&message)
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            escape_data("50% done\r\nnext: a, b"),
+            "50%25 done%0D%0Anext: a, b"
+        );
+        assert_eq!(escape_property("src/a,b:c.rs"), "src/a%2Cb%3Ac.rs");
+    }
+
+    #[test]
+    fn commands() {
+        let mut quotes = finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        quotes.replacements = vec!["“a”".to_owned(), "«a»".to_owned()];
+        let findings = [
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            quotes,
+        ];
+        let stale_entries = [finding("src/main.rs", "RULE", "TYPOS", "old").fingerprint()];
+        let files = ["src/lib.rs".to_owned()];
+        let report = Report {
+            files: &files,
+            n_docs: 1,
+            n_cached_docs: 0,
+            findings: &findings,
+            stale_entries: &stale_entries,
+            group_by: None,
+        };
+
+        let mut output = vec![];
+        write(&report, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("::warning file=src/main.rs,title=STALE_BASELINE::"));
+        assert_eq!(
+            lines[1],
+            "::error file=src/lib.rs,line=1,col=5,endLine=1,endColumn=8,title=MORFOLOGIK_RULE_EN_US::MORFOLOGIK_RULE_EN_US message"
+        );
+        assert_eq!(
+            lines[2],
+            "::notice file=src/lib.rs,line=1,col=5,endLine=1,endColumn=8,title=EN_QUOTES::EN_QUOTES message%0ASuggestions: “a”, «a»"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::xml::escape;
+use super::Report;
+use crate::baseline::STALE_BASELINE_ID;
+use crate::checker::hunspell::HUNSPELL_RULE_ID;
+use crate::doc::LANGUAGE_MISMATCH_ID;
+use crate::finding::{Finding, Severity};
+use crate::rules::is_local_rule;
+
+const STYLE: &str = r"
+body { font-family: sans-serif; margin: 0 auto; max-width: 60em; padding: 1em; color: #222; }
+header { border-bottom: 1px solid #ccc; margin-bottom: 1em; }
+.filters label { margin-right: 1em; }
+.item { margin-left: 1em; }
+.finding { border-left: 4px solid #888; margin: 0.5em 0 1em; padding: 0.25em 0.75em; }
+.finding.error { border-color: #c00; }
+.finding.warning { border-color: #d80; }
+.finding.note { border-color: #08c; }
+.severity { font-weight: bold; text-transform: uppercase; font-size: 0.8em; }
+.location, .category { color: #666; font-size: 0.9em; }
+pre { background: #f6f6f6; padding: 0.5em; overflow-x: auto; }
+mark { background: #fd8; }
+";
+
+const SCRIPT: &str = r#"
+function applyFilters() {
+  const category = document.getElementById("category").value;
+  const rule = document.getElementById("rule").value;
+  for (const finding of document.querySelectorAll(".finding")) {
+    const visible = (!category || finding.dataset.category === category)
+      && (!rule || finding.dataset.rule === rule);
+    finding.hidden = !visible;
+  }
+  for (const group of document.querySelectorAll(".item, .file")) {
+    group.hidden = !group.querySelector(".finding:not([hidden])");
+  }
+}
+document.getElementById("category").addEventListener("change", applyFilters);
+document.getElementById("rule").addEventListener("change", applyFilters);
+"#;
+
+/// Returns the link to the explanation of the rule of a finding, if any.
+///
+/// The rules found by `cargo-languagetool` itself are unknown to the
+/// `languagetool` community site, so they are only linked with their own URLs.
+fn rule_url(finding: &Finding) -> Option<String> {
+    let is_local = is_local_rule(&finding.rule_id)
+        || [HUNSPELL_RULE_ID, LANGUAGE_MISMATCH_ID, STALE_BASELINE_ID]
+            .contains(&finding.rule_id.as_str());
+
+    finding.urls.first().cloned().or_else(|| {
+        (!is_local).then(|| {
+            format!(
+                "https://community.languagetool.org/rule/show/{}",
+                finding.rule_id
+            )
+        })
+    })
+}
+
+/// Returns the rule id of a finding, linked to its explanation if any.
+fn rule_link(finding: &Finding) -> String {
+    rule_url(finding).map_or_else(
+        || escape(&finding.rule_id),
+        |url| {
+            format!(
+                r#"<a href="{}">{}</a>"#,
+                escape(&url),
+                escape(&finding.rule_id)
+            )
+        },
+    )
+}
+
+/// Returns the source context of a finding, with the finding highlighted.
+fn highlighted_context(finding: &Finding) -> String {
+    let context = &finding.context;
+    let end = context.offset + context.length;
+    let before = context.text.get(..context.offset).unwrap_or_default();
+    let matched = context.text.get(context.offset..end).unwrap_or_default();
+    let after = context.text.get(end..).unwrap_or_default();
+
+    format!(
+        "{}<mark>{}</mark>{}",
+        escape(before),
+        escape(matched),
+        escape(after)
+    )
+}
+
+/// Writes the options of a filter.
+fn write_filter(
+    output: &mut dyn Write,
+    id: &str,
+    label: &str,
+    values: &BTreeSet<&str>,
+) -> Result<()> {
+    writeln!(output, r#"<label>{label} <select id="{id}">"#)?;
+    writeln!(output, r#"<option value="">All</option>"#)?;
+    for value in values {
+        let value = escape(value);
+        writeln!(output, r#"<option value="{value}">{value}</option>"#)?;
+    }
+    writeln!(output, "</select></label>")?;
+    Ok(())
+}
+
+/// Writes a finding.
+fn write_finding(output: &mut dyn Write, finding: &Finding) -> Result<()> {
+    let severity = finding.severity.name();
+
+    writeln!(
+        output,
+        r#"<article class="finding {severity}" data-category="{}" data-rule="{}">"#,
+        escape(&finding.category_id),
+        escape(&finding.rule_id)
+    )?;
+    writeln!(
+        output,
+        r#"<p><span class="severity">{severity}</span> {} {} <span class="category">{}</span> <span class="location">line {}, column {}</span></p>"#,
+        escape(&finding.message),
+        rule_link(finding),
+        escape(&finding.category_id),
+        finding.start.line,
+        finding.start.column
+    )?;
+    writeln!(output, "<pre>{}</pre>", highlighted_context(finding))?;
+
+    if !finding.replacements.is_empty() {
+        let replacements: Vec<_> = finding
+            .replacements
+            .iter()
+            .map(|replacement| format!("<code>{}</code>", escape(replacement)))
+            .collect();
+        writeln!(output, "<p>Suggestions: {}</p>", replacements.join(", "))?;
+    }
+
+    writeln!(output, "</article>")?;
+
+    Ok(())
+}
+
+/// Writes the findings as a self-contained HTML page.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let mut files: BTreeMap<String, BTreeMap<&str, Vec<&Finding>>> = BTreeMap::new();
+    for finding in report.findings {
+        files
+            .entry(finding.relative_file())
+            .or_default()
+            .entry(&finding.item_path)
+            .or_default()
+            .push(finding);
+    }
+
+    let categories: BTreeSet<_> = report
+        .findings
+        .iter()
+        .map(|finding| finding.category_id.as_str())
+        .collect();
+    let rules: BTreeSet<_> = report
+        .findings
+        .iter()
+        .map(|finding| finding.rule_id.as_str())
+        .collect();
+
+    let count = |severity| {
+        report
+            .findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    };
+
+    let title = format!("{} report", env!("CARGO_PKG_NAME"));
+
+    writeln!(output, "<!DOCTYPE html>")?;
+    writeln!(output, r#"<html lang="en">"#)?;
+    writeln!(
+        output,
+        r#"<head><meta charset="utf-8"><title>{title}</title>"#
+    )?;
+    writeln!(output, "<style>{STYLE}</style></head>")?;
+    writeln!(output, "<body>")?;
+
+    writeln!(output, "<header><h1>{title}</h1>")?;
+    writeln!(
+        output,
+        "<p>{} findings in {} of {} checked files: {} errors, {} warnings, {} notes.</p>",
+        report.findings.len(),
+        files.len(),
+        report.files.len(),
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Note)
+    )?;
+    writeln!(output, r#"<div class="filters">"#)?;
+    write_filter(output, "category", "Category", &categories)?;
+    write_filter(output, "rule", "Rule", &rules)?;
+    writeln!(output, "</div></header>")?;
+
+    writeln!(output, "<main>")?;
+    for (file, items) in &files {
+        writeln!(output, r#"<section class="file"><h2>{}</h2>"#, escape(file))?;
+        for (item_path, findings) in items {
+            let item_path = if item_path.is_empty() {
+                "(crate)"
+            } else {
+                item_path
+            };
+            writeln!(
+                output,
+                r#"<section class="item"><h3><code>{}</code></h3>"#,
+                escape(item_path)
+            )?;
+            for finding in findings {
+                write_finding(output, finding)?;
+            }
+            writeln!(output, "</section>")?;
+        }
+        writeln!(output, "</section>")?;
+    }
+    writeln!(output, "</main>")?;
+
+    writeln!(output, "<script>{SCRIPT}</script>")?;
+    writeln!(output, "</body>")?;
+    writeln!(output, "</html>")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    #[test]
+    fn rule_urls() {
+        let mut with_url = finding("src/lib.rs", "CODE_IDENTIFIER", "CODE", "get_or");
+        with_url.urls = vec!["https://example.com/rule".to_owned()];
+        assert_eq!(
+            rule_url(&with_url).as_deref(),
+            Some("https://example.com/rule")
+        );
+
+        assert_eq!(
+            rule_url(&finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"")).as_deref(),
+            Some("https://community.languagetool.org/rule/show/EN_QUOTES")
+        );
+
+        for (rule_id, category_id) in [
+            ("HUNSPELL_RULE", "TYPOS"),
+            ("LANGUAGE_MISMATCH", "MISC"),
+            ("STALE_BASELINE", "MISC"),
+            ("CODE_IDENTIFIER", "CODE"),
+            ("GLOSSARY_GITHUB", "TERMINOLOGY"),
+            ("DOC_SUMMARY_VERB", "DOCS"),
+        ] {
+            assert_eq!(
+                rule_url(&finding("src/lib.rs", rule_id, category_id, "a")),
+                None,
+                "{rule_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn page() {
+        let files = ["src/lib.rs".to_owned(), "src/main.rs".to_owned()];
+        let findings = [
+            finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "<a>"),
+            finding("src/lib.rs", "CODE_IDENTIFIER", "CODE", "get_or"),
+        ];
+        let report = Report {
+            files: &files,
+            n_docs: 2,
+            n_cached_docs: 0,
+            findings: &findings,
+            stale_entries: &[],
+            group_by: None,
+        };
+
+        let mut output = vec![];
+        write(&report, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output
+            .contains("<p>2 findings in 1 of 2 checked files: 0 errors, 1 warnings, 1 notes.</p>"));
+        assert_eq!(output.matches(r#"<article class="finding "#).count(), 2);
+        assert!(output.contains(
+            r#"<a href="https://community.languagetool.org/rule/show/EN_QUOTES">EN_QUOTES</a>"#
+        ));
+        assert!(output.contains(" CODE_IDENTIFIER <span class=\"category\">CODE</span>"));
+        assert!(output.contains("<pre>/// <mark>&lt;a&gt;</mark>&#10;</pre>"));
+    }
+}
@@ -0,0 +1,137 @@
+use core::fmt::Write as _;
+use std::collections::HashMap;
+use std::io::Write;
+
+use annotate_snippets::Renderer;
+use color_eyre::Result;
+
+use super::summary::Summary;
+use super::{GroupBy, Report};
+use crate::baseline::annotate_stale_entry;
+use crate::finding::{Finding, Severity};
+
+/// Renders a group of findings as a header and the list of their locations.
+fn annotate_group(
+    group_by: GroupBy,
+    key: &str,
+    findings: &[&Finding],
+    renderer: &Renderer,
+) -> String {
+    let level = findings
+        .iter()
+        .map(|finding| finding.severity)
+        .max()
+        .unwrap_or(Severity::Note)
+        .level();
+
+    let title = match group_by {
+        GroupBy::Rule => format!("{} findings: {}", findings.len(), findings[0].message),
+        GroupBy::File | GroupBy::Category => format!("{} findings", findings.len()),
+    };
+
+    let header = renderer.render(level.title(&title).id(key)).to_string();
+
+    let mut group = header;
+    for finding in findings {
+        write!(
+            group,
+            "\n  --> {}:{}:{} [{}:{}] {}",
+            finding.relative_file(),
+            finding.start.line,
+            finding.start.column,
+            finding.rule_id,
+            finding.category_id,
+            finding.message
+        )
+        .ok();
+        if !finding.replacements.is_empty() {
+            write!(group, " ({})", finding.replacements.join(", ")).ok();
+        }
+    }
+    group.push('\n');
+    group
+}
+
+/// Writes the findings as annotated snippets of the source, and a summary.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write, styled: bool) -> Result<()> {
+    let renderer = if styled {
+        Renderer::styled()
+    } else {
+        Renderer::plain()
+    };
+
+    for entry in report.stale_entries {
+        writeln!(output, "{}", annotate_stale_entry(entry, &renderer))?;
+    }
+
+    if let Some(group_by) = report.group_by {
+        let mut indices = HashMap::new();
+        let mut groups: Vec<(String, Vec<&Finding>)> = vec![];
+        for finding in report.findings {
+            let key = group_by.key(finding);
+            let index = *indices.entry(key.clone()).or_insert_with(|| {
+                groups.push((key, vec![]));
+                groups.len() - 1
+            });
+            groups[index].1.push(finding);
+        }
+
+        for (key, findings) in &groups {
+            writeln!(
+                output,
+                "{}",
+                annotate_group(group_by, key, findings, &renderer)
+            )?;
+        }
+    } else {
+        for finding in report.findings {
+            writeln!(output, "{}", finding.annotate(&renderer))?;
+        }
+    }
+
+    writeln!(output, "{}", Summary::from(report))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    fn findings() -> [Finding; 2] {
+        let mut quotes = finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        quotes.replacements = vec!["“a”".to_owned()];
+        [
+            quotes,
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+        ]
+    }
+
+    #[test]
+    fn snippets() {
+        let output = written(Format::Human, &findings(), None);
+
+        assert!(output.contains(
+            "error[MORFOLOGIK_RULE_EN_US:TYPOS]: MORFOLOGIK_RULE_EN_US message\n --> src/lib.rs:1:5\n"
+        ));
+        assert!(output.contains("note[EN_QUOTES:TYPOGRAPHY]: EN_QUOTES message"));
+        assert!(output.contains("help: “a”"));
+        assert!(output.contains("Found 2 findings: 1 errors, 0 warnings, 1 notes."));
+    }
+
+    #[test]
+    fn groups() {
+        let output = written(Format::Human, &findings(), Some(GroupBy::File));
+
+        assert!(output.contains("error[src/lib.rs]: 2 findings\n"));
+        assert!(output.contains(
+            "  --> src/lib.rs:1:5 [EN_QUOTES:TYPOGRAPHY] EN_QUOTES message (“a”)\n  --> src/lib.rs:1:5 [MORFOLOGIK_RULE_EN_US:TYPOS] MORFOLOGIK_RULE_EN_US message\n"
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::Write;
+
+use color_eyre::Result;
+use serde::Serialize;
+
+use super::summary::Summary;
+use super::Report;
+use crate::finding::{Finding, Fingerprint};
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    checked_files: usize,
+    findings: &'a [Finding],
+    stale_baseline_entries: &'a [Fingerprint],
+    summary: Summary,
+}
+
+/// Writes the findings as a JSON document.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let json_report = JsonReport {
+        checked_files: report.files.len(),
+        findings: report.findings,
+        stale_baseline_entries: report.stale_entries,
+        summary: Summary::from(report),
+    };
+
+    serde_json::to_writer_pretty(&mut *output, &json_report)?;
+    writeln!(output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn document() {
+        let findings = [finding(
+            "src/lib.rs",
+            "MORFOLOGIK_RULE_EN_US",
+            "TYPOS",
+            "teh",
+        )];
+
+        let output: Value = serde_json::from_str(&written(Format::Json, &findings, None)).unwrap();
+
+        assert_eq!(output["checked_files"], 2);
+        assert_eq!(output["findings"][0]["rule_id"], "MORFOLOGIK_RULE_EN_US");
+        assert_eq!(output["findings"][0]["severity"], "error");
+        assert_eq!(output["findings"][0]["start"]["column"], 5);
+        assert_eq!(output["findings"][0]["byte_range"]["start"], 4);
+        assert_eq!(output["stale_baseline_entries"], Value::Array(vec![]));
+        assert_eq!(output["summary"]["n_errors"], 1);
+        assert_eq!(output["summary"]["files"]["src/lib.rs"], 1);
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::xml::escape;
+use super::Report;
+use crate::finding::Finding;
+
+/// Returns the text of the failure of a finding.
+fn failure_text(finding: &Finding) -> String {
+    let mut text = format!(
+        "{}:{}:{}: {}\nitem: {}\nrule: {}:{}\nmatched: {}",
+        finding.relative_file(),
+        finding.start.line,
+        finding.start.column,
+        finding.message,
+        finding.item_path,
+        finding.rule_id,
+        finding.category_id,
+        finding.matched_text
+    );
+    if !finding.replacements.is_empty() {
+        text.push_str("\nsuggestions: ");
+        text.push_str(&finding.replacements.join(", "));
+    }
+    text
+}
+
+/// Writes the findings as a `JUnit` XML report.
+///
+/// Each checked file is a test case, which fails with a failure per finding
+/// in the file.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let mut files: BTreeMap<&str, Vec<&Finding>> = report
+        .files
+        .iter()
+        .map(|file| (file.as_str(), vec![]))
+        .collect();
+    for finding in report.findings {
+        files.entry(&finding.file).or_default().push(finding);
+    }
+
+    let n_tests = files.len();
+    let n_failures = files
+        .values()
+        .filter(|findings| !findings.is_empty())
+        .count();
+
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        output,
+        r#"<testsuites name="{name}" tests="{n_tests}" failures="{n_failures}">"#,
+        name = env!("CARGO_PKG_NAME")
+    )?;
+    writeln!(
+        output,
+        r#"  <testsuite name="{name}" tests="{n_tests}" failures="{n_failures}" errors="0" skipped="0">"#,
+        name = env!("CARGO_PKG_NAME")
+    )?;
+
+    for (file, findings) in files {
+        let file = escape(file.strip_prefix("./").unwrap_or(file));
+
+        if findings.is_empty() {
+            writeln!(
+                output,
+                r#"    <testcase classname="{file}" name="{file}"/>"#
+            )?;
+            continue;
+        }
+
+        writeln!(output, r#"    <testcase classname="{file}" name="{file}">"#)?;
+        for finding in findings {
+            writeln!(
+                output,
+                r#"      <failure message="{}" type="{}">{}</failure>"#,
+                escape(&format!(
+                    "{}:{}: {}",
+                    finding.start.line, finding.start.column, finding.message
+                )),
+                escape(&finding.rule_id),
+                escape(&failure_text(finding))
+            )?;
+        }
+        writeln!(output, "    </testcase>")?;
+    }
+
+    writeln!(output, "  </testsuite>")?;
+    writeln!(output, "</testsuites>")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    #[test]
+    fn one_test_case_per_file() {
+        let files = ["./src/lib.rs".to_owned(), "./src/main.rs".to_owned()];
+        let findings = [
+            finding("./src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            finding("./src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\""),
+        ];
+        let report = Report {
+            files: &files,
+            n_docs: 2,
+            n_cached_docs: 0,
+            findings: &findings,
+            stale_entries: &[],
+            group_by: None,
+        };
+
+        let mut output = vec![];
+        write(&report, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#"<testsuite name="cargo-languagetool" tests="2" failures="1""#));
+        assert_eq!(output.matches("<testcase ").count(), 2);
+        assert_eq!(output.matches("<failure ").count(), 2);
+        assert!(output.contains(r#"<testcase classname="src/lib.rs" name="src/lib.rs">"#));
+        assert!(output.contains(r#"<testcase classname="src/main.rs" name="src/main.rs"/>"#));
+        assert!(output.contains(
+            r#"<failure message="1:5: EN_QUOTES message" type="EN_QUOTES">src/lib.rs:1:5: EN_QUOTES message"#
+        ));
+        assert!(output.contains("matched: &quot;a&quot;"));
+    }
+}
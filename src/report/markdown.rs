@@ -0,0 +1,191 @@
+use core::fmt::Write as _;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::Report;
+use crate::finding::{Finding, Severity};
+
+/// Maximum length of the report, to fit in a pull request comment.
+const MAX_LENGTH: usize = 60_000;
+
+/// Room kept at the end of the report for the note about the omitted findings.
+const TRUNCATION_NOTE_LENGTH: usize = 100;
+
+/// Returns the source lines of a finding with its first replacement applied.
+fn corrected_lines(finding: &Finding) -> Option<String> {
+    let replacement = finding.replacements.first()?;
+    let context = &finding.context;
+
+    let lines = context
+        .text
+        .lines()
+        .take(finding.end.line - finding.start.line + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let before = lines.get(..context.offset)?;
+    let after = lines.get(context.offset + context.length..)?;
+
+    Some(format!("{before}{replacement}{after}"))
+}
+
+/// Renders a finding as a Markdown entry.
+fn entry(finding: &Finding) -> String {
+    let mut entry = format!(
+        "#### `{}:{}:{}` {} ({})\n\n**{}**: {}\n",
+        finding.relative_file(),
+        finding.start.line,
+        finding.start.column,
+        finding.rule_id,
+        finding.category_id,
+        finding.severity.name(),
+        finding.message
+    );
+
+    if let Some(corrected_lines) = corrected_lines(finding) {
+        write!(entry, "\n```suggestion\n{corrected_lines}\n```\n").ok();
+    }
+
+    entry.push('\n');
+    entry
+}
+
+/// Renders a table of the number of findings per key.
+fn count_table<'a>(header: &str, keys: impl Iterator<Item = &'a str>) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for key in keys {
+        *counts.entry(key).or_default() += 1;
+    }
+
+    let mut table = format!("| {header} | Findings |\n| --- | ---: |\n");
+    for (key, count) in counts {
+        writeln!(table, "| `{}` | {count} |", key.replace('|', "\\|")).ok();
+    }
+    table.push('\n');
+    table
+}
+
+/// Writes a compact Markdown summary of the findings, e.g. for a pull request
+/// comment.
+///
+/// The findings which do not fit in a comment are only counted.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let count = |severity| {
+        report
+            .findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    };
+
+    let mut markdown = format!("## {}\n\n", env!("CARGO_PKG_NAME"));
+
+    if report.findings.is_empty() {
+        writeln!(
+            markdown,
+            "No findings in {} checked files.",
+            report.files.len()
+        )?;
+        output.write_all(markdown.as_bytes())?;
+        return Ok(());
+    }
+
+    let relative_files: Vec<_> = report.findings.iter().map(Finding::relative_file).collect();
+
+    writeln!(
+        markdown,
+        "{} findings in {} checked files: {} errors, {} warnings, {} notes.\n",
+        report.findings.len(),
+        report.files.len(),
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Note)
+    )?;
+
+    markdown.push_str(&count_table(
+        "Category",
+        report
+            .findings
+            .iter()
+            .map(|finding| finding.category_id.as_str()),
+    ));
+    markdown.push_str(&count_table(
+        "File",
+        relative_files.iter().map(String::as_str),
+    ));
+
+    markdown.push_str("### Findings\n\n");
+
+    for (index, finding) in report.findings.iter().enumerate() {
+        let entry = entry(finding);
+        if markdown.len() + entry.len() + TRUNCATION_NOTE_LENGTH > MAX_LENGTH {
+            writeln!(
+                markdown,
+                "_{} more findings are not shown._",
+                report.findings.len() - index
+            )?;
+            break;
+        }
+        markdown.push_str(&entry);
+    }
+
+    output.write_all(markdown.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn no_findings() {
+        assert_eq!(
+            written(Format::Markdown, &[], None),
+            "## cargo-languagetool\n\nNo findings in 2 checked files.\n"
+        );
+    }
+
+    #[test]
+    fn summary_and_suggestions() {
+        let mut typo = finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh");
+        typo.replacements = vec!["the".to_owned()];
+        typo.context.text = "/// café teh value\n/// next line\n".to_owned();
+        typo.context.offset = 10;
+
+        let output = written(Format::Markdown, &[typo], None);
+
+        assert!(output.contains("1 findings in 2 checked files: 1 errors, 0 warnings, 0 notes.\n"));
+        assert!(output.contains("| `TYPOS` | 1 |\n"));
+        assert!(output.contains("| `src/lib.rs` | 1 |\n"));
+        assert!(output.contains(
+            "#### `src/lib.rs:1:5` MORFOLOGIK_RULE_EN_US (TYPOS)\n\n**error**: MORFOLOGIK_RULE_EN_US message\n"
+        ));
+        assert!(output.contains("```suggestion\n/// café the value\n```\n"));
+    }
+
+    #[test]
+    fn truncated() {
+        let findings: Vec<_> = (0..10)
+            .map(|_| {
+                let mut typo = finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh");
+                typo.message = "m".repeat(10_000);
+                typo
+            })
+            .collect();
+
+        let output = written(Format::Markdown, &findings, None);
+
+        assert!(output.len() <= MAX_LENGTH);
+        assert_eq!(output.matches("#### ").count(), 5);
+        assert!(output.ends_with("_5 more findings are not shown._\n"));
+    }
+}
@@ -0,0 +1,241 @@
+//! The `report` module writes the results of a run in the output formats.
+
+pub mod checkstyle;
+pub mod github;
+pub mod html;
+pub mod human;
+pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod rdjson;
+pub mod rustc;
+pub mod sarif;
+pub mod short;
+pub mod summary;
+mod xml;
+
+use core::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::Write;
+
+use clap::ValueEnum;
+use color_eyre::Result;
+use serde::Deserialize;
+
+use crate::finding::{Finding, Fingerprint};
+
+/// Output format of the results.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Annotated snippets of the source, as `rustc` prints its diagnostics.
+    #[default]
+    Human,
+    /// One line per finding: `file:line:column: severity [RULE:CATEGORY] message`.
+    Short,
+    /// A JSON document with the findings.
+    Json,
+    /// A SARIF 2.1.0 log, e.g. for GitHub code scanning.
+    Sarif,
+    /// `rustc` JSON diagnostics, one per line, as `cargo check --message-format=json`.
+    Rustc,
+    /// GitHub Actions workflow commands, which annotate the pull request.
+    Github,
+    /// A `JUnit` XML report, with a failed test case per finding.
+    Junit,
+    /// A Checkstyle XML report.
+    Checkstyle,
+    /// The reviewdog diagnostic format, with the replacements as suggestions.
+    Rdjson,
+    /// A self-contained HTML page, e.g. for reviews with non-engineers.
+    Html,
+    /// A compact Markdown summary, e.g. for a pull request comment.
+    Markdown,
+}
+
+/// The output formats of `cargo check --message-format`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    Human,
+    Short,
+    Json,
+}
+
+impl From<MessageFormat> for Format {
+    fn from(message_format: MessageFormat) -> Self {
+        match message_format {
+            MessageFormat::Human => Self::Human,
+            MessageFormat::Short => Self::Short,
+            MessageFormat::Json => Self::Rustc,
+        }
+    }
+}
+
+/// How the findings are grouped in the human output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Rule,
+    File,
+    Category,
+}
+
+impl GroupBy {
+    /// The key of the group of a finding.
+    #[must_use]
+    pub fn key(self, finding: &Finding) -> String {
+        match self {
+            Self::Rule => finding.rule_id.clone(),
+            Self::File => finding.relative_file(),
+            Self::Category => finding.category_id.clone(),
+        }
+    }
+}
+
+/// Order of the findings.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    /// By file, then by position in the file.
+    #[default]
+    Location,
+    /// Errors first, then warnings, then notes.
+    Severity,
+    Rule,
+    Category,
+    /// The findings of the largest groups, or of the most frequent rules, first.
+    Count,
+}
+
+impl SortBy {
+    /// Sorts the findings. The sort is stable, so the findings are in order of
+    /// location for equal keys.
+    pub fn sort(self, findings: &mut [Finding], group_by: Option<GroupBy>) {
+        findings
+            .sort_by(|first, second| (&first.file, first.start).cmp(&(&second.file, second.start)));
+
+        match self {
+            Self::Location => {}
+            Self::Severity => findings.sort_by_key(|finding| Reverse(finding.severity)),
+            Self::Rule => findings.sort_by(|first, second| first.rule_id.cmp(&second.rule_id)),
+            Self::Category => {
+                findings.sort_by(|first, second| first.category_id.cmp(&second.category_id));
+            }
+            Self::Count => {
+                let group_by = group_by.unwrap_or(GroupBy::Rule);
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                for finding in findings.iter() {
+                    *counts.entry(group_by.key(finding)).or_default() += 1;
+                }
+                findings.sort_by_cached_key(|finding| {
+                    let key = group_by.key(finding);
+                    (Reverse(counts[&key]), key)
+                });
+            }
+        }
+    }
+}
+
+/// The results of a run.
+#[derive(Debug)]
+pub struct Report<'a> {
+    /// The checked files.
+    pub files: &'a [String],
+    /// Number of checked doc blocks.
+    pub n_docs: usize,
+    /// Number of doc blocks whose check came from the cache.
+    pub n_cached_docs: usize,
+    /// Findings which are not in the baseline, if any.
+    pub findings: &'a [Finding],
+    /// Baseline entries which no longer match any finding.
+    pub stale_entries: &'a [Fingerprint],
+    /// Grouping of the findings in the human output.
+    pub group_by: Option<GroupBy>,
+}
+
+impl Format {
+    /// Writes the report in the format.
+    ///
+    /// `styled` enables colors, for the formats which support them.
+    ///
+    /// # Errors
+    /// If the report cannot be written.
+    pub fn write(self, report: &Report<'_>, output: &mut dyn Write, styled: bool) -> Result<()> {
+        match self {
+            Self::Human => human::write(report, output, styled),
+            Self::Short => short::write(report, output),
+            Self::Json => json::write(report, output),
+            Self::Sarif => sarif::write(report, output),
+            Self::Rustc => rustc::write(report, output),
+            Self::Github => github::write(report, output),
+            Self::Junit => junit::write(report, output),
+            Self::Checkstyle => checkstyle::write(report, output),
+            Self::Rdjson => rdjson::write(report, output),
+            Self::Html => html::write(report, output),
+            Self::Markdown => markdown::write(report, output),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    /// Writes a report of the findings of `src/lib.rs` and `src/main.rs`.
+    ///
+    /// # Panics
+    /// If the report cannot be written.
+    #[must_use]
+    pub fn written(format: Format, findings: &[Finding], group_by: Option<GroupBy>) -> String {
+        let files = ["src/lib.rs".to_owned(), "src/main.rs".to_owned()];
+        let report = Report {
+            files: &files,
+            n_docs: 2,
+            n_cached_docs: 0,
+            findings,
+            stale_entries: &[],
+            group_by,
+        };
+
+        let mut output = vec![];
+        format.write(&report, &mut output, false).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Returns the rule ids of the findings, sorted.
+    fn sorted(sort: SortBy, group_by: Option<GroupBy>) -> Vec<String> {
+        let mut findings: Vec<_> = [
+            ("src/main.rs", 1, "B", "TYPOS"),
+            ("src/lib.rs", 3, "A", "STYLE"),
+            ("src/lib.rs", 1, "C", "TYPOGRAPHY"),
+            ("src/lib.rs", 2, "B", "TYPOS"),
+        ]
+        .into_iter()
+        .map(|(file, line, rule_id, category_id)| {
+            let mut finding = finding(file, rule_id, category_id, "a");
+            finding.start.line = line;
+            finding
+        })
+        .collect();
+
+        sort.sort(&mut findings, group_by);
+        findings
+            .iter()
+            .map(|finding| format!("{}:{}", finding.rule_id, finding.start.line))
+            .collect()
+    }
+
+    #[test]
+    fn sorts() {
+        assert_eq!(sorted(SortBy::Location, None), ["C:1", "B:2", "A:3", "B:1"]);
+        assert_eq!(sorted(SortBy::Severity, None), ["B:2", "B:1", "A:3", "C:1"]);
+        assert_eq!(sorted(SortBy::Rule, None), ["A:3", "B:2", "B:1", "C:1"]);
+        assert_eq!(sorted(SortBy::Category, None), ["A:3", "C:1", "B:2", "B:1"]);
+        assert_eq!(sorted(SortBy::Count, None), ["B:2", "B:1", "A:3", "C:1"]);
+        assert_eq!(
+            sorted(SortBy::Count, Some(GroupBy::File)),
+            ["C:1", "B:2", "A:3", "B:1"]
+        );
+    }
+}
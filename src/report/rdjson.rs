@@ -0,0 +1,141 @@
+use std::io::Write;
+
+use color_eyre::Result;
+use serde_json::{json, Value};
+
+use super::Report;
+use crate::finding::{Finding, Position, Severity};
+
+/// The reviewdog severity of a severity.
+const fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Note => "INFO",
+        Severity::Warning => "WARNING",
+        Severity::Error => "ERROR",
+    }
+}
+
+/// Returns the reviewdog position of a position of the finding.
+///
+/// reviewdog counts the columns in bytes, while the findings count them in
+/// characters, so they are converted with the source lines of the finding.
+fn position(finding: &Finding, position: Position) -> Value {
+    let column = finding
+        .context
+        .text
+        .lines()
+        .nth(position.line - finding.start.line)
+        .map_or(position.column, |line| {
+            1 + line
+                .chars()
+                .take(position.column - 1)
+                .map(char::len_utf8)
+                .sum::<usize>()
+        });
+
+    json!({
+        "line": position.line,
+        "column": column,
+    })
+}
+
+/// Returns the reviewdog diagnostic of a finding.
+fn diagnostic(finding: &Finding) -> Value {
+    let range = json!({
+        "start": position(finding, finding.start),
+        "end": position(finding, finding.end),
+    });
+
+    let suggestions: Vec<_> = finding
+        .replacements
+        .iter()
+        .map(|replacement| {
+            json!({
+                "range": range,
+                "text": replacement,
+            })
+        })
+        .collect();
+
+    let mut code = json!({ "value": finding.rule_id });
+    if let Some(url) = finding.urls.first() {
+        code["url"] = json!(url);
+    }
+
+    json!({
+        "message": finding.message,
+        "location": {
+            "path": finding.relative_file(),
+            "range": range,
+        },
+        "severity": severity(finding.severity),
+        "code": code,
+        "suggestions": suggestions,
+    })
+}
+
+/// Writes the findings in the reviewdog diagnostic format, `rdjson`.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let result = json!({
+        "source": {
+            "name": env!("CARGO_PKG_NAME"),
+            "url": env!("CARGO_PKG_REPOSITORY"),
+        },
+        "diagnostics": report.findings.iter().map(diagnostic).collect::<Vec<_>>(),
+    });
+
+    serde_json::to_writer_pretty(&mut *output, &result)?;
+    writeln!(output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn diagnostics() {
+        let mut typo = finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh");
+        typo.replacements = vec!["the".to_owned(), "ten".to_owned()];
+        typo.urls = vec!["https://example.com/rule".to_owned()];
+
+        let result: Value = serde_json::from_str(&written(Format::Rdjson, &[typo], None)).unwrap();
+        let diagnostic = &result["diagnostics"][0];
+
+        assert_eq!(result["source"]["name"], "cargo-languagetool");
+        assert_eq!(diagnostic["severity"], "ERROR");
+        assert_eq!(diagnostic["location"]["path"], "src/lib.rs");
+        assert_eq!(
+            diagnostic["code"],
+            json!({ "value": "MORFOLOGIK_RULE_EN_US", "url": "https://example.com/rule" })
+        );
+        assert_eq!(diagnostic["suggestions"][1]["text"], "ten");
+        assert_eq!(
+            diagnostic["suggestions"][0]["range"],
+            diagnostic["location"]["range"]
+        );
+    }
+
+    #[test]
+    fn byte_columns() {
+        let mut typo = finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh");
+        typo.context.text = "/// café teh\n".to_owned();
+        typo.start.column = 10;
+        typo.end.column = 13;
+
+        assert_eq!(
+            diagnostic(&typo)["location"]["range"],
+            json!({
+                "start": { "line": 1, "column": 11 },
+                "end": { "line": 1, "column": 14 },
+            })
+        );
+    }
+}
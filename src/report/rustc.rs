@@ -0,0 +1,151 @@
+use std::io::Write;
+
+use annotate_snippets::Renderer;
+use color_eyre::Result;
+use serde_json::{json, Value};
+
+use super::Report;
+use crate::finding::Finding;
+
+/// Returns a `rustc` span of the finding, with a suggested replacement.
+fn span(finding: &Finding, label: Option<&str>, replacement: Option<&str>) -> Value {
+    let n_lines = finding.end.line - finding.start.line + 1;
+
+    let text: Vec<_> = finding
+        .context
+        .text
+        .lines()
+        .take(n_lines)
+        .enumerate()
+        .map(|(index, line)| {
+            let highlight_start = if index == 0 { finding.start.column } else { 1 };
+            let highlight_end = if index + 1 == n_lines {
+                finding.end.column
+            } else {
+                line.chars().count() + 1
+            };
+            json!({
+                "text": line,
+                "highlight_start": highlight_start,
+                "highlight_end": highlight_end,
+            })
+        })
+        .collect();
+
+    json!({
+        "file_name": finding.relative_file(),
+        "byte_start": finding.byte_range.start,
+        "byte_end": finding.byte_range.end,
+        "line_start": finding.start.line,
+        "line_end": finding.end.line,
+        "column_start": finding.start.column,
+        "column_end": finding.end.column,
+        "is_primary": true,
+        "text": text,
+        "label": label,
+        "suggested_replacement": replacement,
+        "suggestion_applicability": replacement.map(|_| "MaybeIncorrect"),
+        "expansion": null,
+    })
+}
+
+/// Returns the `rustc` diagnostic of a finding.
+fn diagnostic(finding: &Finding, renderer: &Renderer) -> Value {
+    let children: Vec<_> = if finding.replacements.is_empty() {
+        vec![]
+    } else {
+        vec![json!({
+            "message": "try",
+            "code": null,
+            "level": "help",
+            "spans": finding
+                .replacements
+                .iter()
+                .map(|replacement| span(finding, None, Some(replacement)))
+                .collect::<Vec<_>>(),
+            "children": [],
+            "rendered": null,
+        })]
+    };
+
+    json!({
+        "$message_type": "diagnostic",
+        "message": finding.message,
+        "code": {
+            "code": finding.rule_id,
+            "explanation": null,
+        },
+        "level": finding.severity.name(),
+        "spans": [span(finding, Some(&finding.description), None)],
+        "children": children,
+        "rendered": finding.annotate(renderer),
+    })
+}
+
+/// Writes the findings as `rustc` JSON diagnostics, one per line.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let renderer = Renderer::plain();
+
+    for finding in report.findings {
+        serde_json::to_writer(&mut *output, &diagnostic(finding, &renderer))?;
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn diagnostics() {
+        let mut quotes = finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        quotes.replacements = vec!["“a”".to_owned(), "«a»".to_owned()];
+        let findings = [
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            quotes,
+        ];
+
+        let output = written(Format::Rustc, &findings, None);
+        let diagnostics: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(diagnostics.len(), 2);
+
+        let typo = &diagnostics[0];
+        assert_eq!(typo["$message_type"], "diagnostic");
+        assert_eq!(typo["level"], "error");
+        assert_eq!(typo["code"]["code"], "MORFOLOGIK_RULE_EN_US");
+        assert_eq!(typo["children"], json!([]));
+        assert!(typo["rendered"]
+            .as_str()
+            .unwrap()
+            .starts_with("error[MORFOLOGIK_RULE_EN_US:TYPOS]"));
+
+        let span = &typo["spans"][0];
+        assert_eq!(span["file_name"], "src/lib.rs");
+        assert_eq!(span["byte_start"], 4);
+        assert_eq!(span["byte_end"], 7);
+        assert_eq!(span["column_start"], 5);
+        assert_eq!(span["column_end"], 8);
+        assert_eq!(
+            span["text"],
+            json!([{ "text": "/// teh", "highlight_start": 5, "highlight_end": 8 }])
+        );
+
+        let suggestions = diagnostics[1]["children"][0]["spans"].as_array().unwrap();
+        assert_eq!(diagnostics[1]["level"], "note");
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0]["suggested_replacement"], "“a”");
+        assert_eq!(suggestions[1]["suggestion_applicability"], "MaybeIncorrect");
+    }
+}
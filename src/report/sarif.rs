@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use color_eyre::Result;
+use serde_json::{json, Value};
+
+use super::Report;
+use crate::finding::{Finding, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The SARIF level of a severity.
+const fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Note => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Returns the rule descriptor of a finding.
+fn rule(finding: &Finding) -> Value {
+    let mut rule = json!({
+        "id": finding.rule_id,
+        "shortDescription": { "text": finding.description },
+        "properties": {
+            "category": finding.category_id,
+            "tags": [finding.category_id],
+        },
+    });
+
+    if let Some(url) = finding.urls.first() {
+        rule["helpUri"] = json!(url);
+    }
+
+    rule
+}
+
+/// Writes the findings as a SARIF 2.1.0 log.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    let mut rule_indices = BTreeMap::new();
+    let mut rules = vec![];
+
+    let results: Vec<_> = report
+        .findings
+        .iter()
+        .map(|finding| {
+            let rule_index = *rule_indices
+                .entry(finding.rule_id.as_str())
+                .or_insert_with(|| {
+                    rules.push(rule(finding));
+                    rules.len() - 1
+                });
+
+            json!({
+                "ruleId": finding.rule_id,
+                "ruleIndex": rule_index,
+                "level": level(finding.severity),
+                "message": { "text": finding.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": finding.relative_file(),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": {
+                            "startLine": finding.start.line,
+                            "startColumn": finding.start.column,
+                            "endLine": finding.end.line,
+                            "endColumn": finding.end.column,
+                            "snippet": { "text": finding.matched_text },
+                        },
+                    },
+                    "logicalLocations": [{
+                        "fullyQualifiedName": finding.item_path,
+                    }],
+                }],
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            // the columns of the findings are counted in characters.
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    });
+
+    serde_json::to_writer_pretty(&mut *output, &log)?;
+    writeln!(output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn log() {
+        let mut with_url = finding("src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        with_url.urls = vec!["https://example.com/rule".to_owned()];
+        let findings = [
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            with_url,
+            finding("src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "adn"),
+        ];
+
+        let log: Value = serde_json::from_str(&written(Format::Sarif, &findings, None)).unwrap();
+        let run = &log["runs"][0];
+
+        assert_eq!(log["version"], SARIF_VERSION);
+        assert_eq!(run["columnKind"], "unicodeCodePoints");
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1]["id"], "EN_QUOTES");
+        assert_eq!(rules[1]["helpUri"], "https://example.com/rule");
+        assert_eq!(rules[0].get("helpUri"), None);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2]["ruleIndex"], 0);
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[0]["level"], "error");
+
+        let location = &results[0]["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+        assert_eq!(location["physicalLocation"]["region"]["startColumn"], 5);
+        assert_eq!(location["physicalLocation"]["region"]["endColumn"], 8);
+        assert_eq!(
+            location["physicalLocation"]["region"]["snippet"]["text"],
+            "teh"
+        );
+        assert_eq!(location["logicalLocations"][0]["fullyQualifiedName"], "foo");
+    }
+}
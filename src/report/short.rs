@@ -0,0 +1,64 @@
+use std::io::Write;
+
+use color_eyre::Result;
+
+use super::Report;
+use crate::baseline::{stale_entry_message, STALE_BASELINE_ID};
+
+/// Writes the findings one per line, e.g. for the quickfix list of an editor.
+///
+/// # Errors
+/// If the report cannot be written.
+pub fn write(report: &Report<'_>, output: &mut dyn Write) -> Result<()> {
+    for entry in report.stale_entries {
+        writeln!(
+            output,
+            "{}: warning [{STALE_BASELINE_ID}] {}",
+            entry.file,
+            stale_entry_message(entry)
+        )?;
+    }
+
+    for finding in report.findings {
+        write!(
+            output,
+            "{}:{}:{}: {} [{}:{}] {}",
+            finding.relative_file(),
+            finding.start.line,
+            finding.start.column,
+            finding.severity.name(),
+            finding.rule_id,
+            finding.category_id,
+            finding.message
+        )?;
+        if !finding.replacements.is_empty() {
+            write!(output, " ({})", finding.replacements.join(", "))?;
+        }
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finding::tests::finding;
+    use crate::report::tests::written;
+    use crate::report::Format;
+
+    #[test]
+    fn lines() {
+        let mut quotes = finding("./src/lib.rs", "EN_QUOTES", "TYPOGRAPHY", "\"a\"");
+        quotes.replacements = vec!["“a”".to_owned(), "«a»".to_owned()];
+        let findings = [
+            finding("./src/lib.rs", "MORFOLOGIK_RULE_EN_US", "TYPOS", "teh"),
+            quotes,
+        ];
+
+        assert_eq!(
+            written(Format::Short, &findings, None),
+            "src/lib.rs:1:5: error [MORFOLOGIK_RULE_EN_US:TYPOS] MORFOLOGIK_RULE_EN_US message\n\
+             src/lib.rs:1:5: note [EN_QUOTES:TYPOGRAPHY] EN_QUOTES message (“a”, «a»)\n"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::Report;
+use crate::finding::Severity;
+
+/// Number of rules listed in the summary.
+const TOP_RULES: usize = 5;
+
+/// Statistics of a run.
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub n_files: usize,
+    pub n_docs: usize,
+    pub n_cached_docs: usize,
+    pub n_findings: usize,
+    pub n_errors: usize,
+    pub n_warnings: usize,
+    pub n_notes: usize,
+    pub categories: BTreeMap<String, usize>,
+    pub rules: BTreeMap<String, usize>,
+    pub files: BTreeMap<String, usize>,
+}
+
+impl From<&Report<'_>> for Summary {
+    fn from(report: &Report<'_>) -> Self {
+        let mut summary = Self {
+            n_files: report.files.len(),
+            n_docs: report.n_docs,
+            n_cached_docs: report.n_cached_docs,
+            n_findings: report.findings.len(),
+            n_errors: 0,
+            n_warnings: 0,
+            n_notes: 0,
+            categories: BTreeMap::new(),
+            rules: BTreeMap::new(),
+            files: BTreeMap::new(),
+        };
+
+        for finding in report.findings {
+            match finding.severity {
+                Severity::Error => summary.n_errors += 1,
+                Severity::Warning => summary.n_warnings += 1,
+                Severity::Note => summary.n_notes += 1,
+            }
+            *summary
+                .categories
+                .entry(finding.category_id.clone())
+                .or_default() += 1;
+            *summary.rules.entry(finding.rule_id.clone()).or_default() += 1;
+            *summary.files.entry(finding.relative_file()).or_default() += 1;
+        }
+
+        summary
+    }
+}
+
+/// Lists the counts, the largest first.
+fn counts(counts: &BTreeMap<String, usize>, limit: usize) -> String {
+    let mut counts: Vec<_> = counts.iter().collect();
+    counts.sort_by(|first, second| second.1.cmp(first.1));
+
+    counts
+        .into_iter()
+        .take(limit)
+        .map(|(key, count)| format!("{key} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl core::fmt::Display for Summary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "Checked {} files and {} docs ({} served from the cache).",
+            self.n_files, self.n_docs, self.n_cached_docs
+        )?;
+
+        if self.n_findings == 0 {
+            return write!(f, "No findings.");
+        }
+
+        writeln!(
+            f,
+            "Found {} findings: {} errors, {} warnings, {} notes.",
+            self.n_findings, self.n_errors, self.n_warnings, self.n_notes
+        )?;
+        writeln!(f, "Per category: {}", counts(&self.categories, usize::MAX))?;
+        writeln!(f, "Per file: {}", counts(&self.files, usize::MAX))?;
+        write!(f, "Top rules: {}", counts(&self.rules, TOP_RULES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::tests::finding;
+
+    #[test]
+    fn statistics() {
+        let findings: Vec<_> = ["A", "B", "B", "C", "C", "C", "D", "E", "F"]
+            .into_iter()
+            .map(|rule_id| finding("./src/lib.rs", rule_id, "TYPOS", "teh"))
+            .chain([finding("src/main.rs", "G", "STYLE", "it")])
+            .collect();
+        let files = ["src/lib.rs".to_owned(), "src/main.rs".to_owned()];
+        let report = Report {
+            files: &files,
+            n_docs: 4,
+            n_cached_docs: 1,
+            findings: &findings,
+            stale_entries: &[],
+            group_by: None,
+        };
+
+        let summary = Summary::from(&report);
+
+        assert_eq!(
+            (summary.n_errors, summary.n_warnings, summary.n_notes),
+            (9, 1, 0)
+        );
+        assert_eq!(summary.files["src/lib.rs"], 9);
+        assert_eq!(
+            summary.to_string(),
+            "Checked 2 files and 4 docs (1 served from the cache).\n\
+             Found 10 findings: 9 errors, 1 warnings, 0 notes.\n\
+             Per category: TYPOS (9), STYLE (1)\n\
+             Per file: src/lib.rs (9), src/main.rs (1)\n\
+             Top rules: C (3), B (2), A (1), D (1), E (1)"
+        );
+    }
+
+    #[test]
+    fn no_findings() {
+        let files = ["src/lib.rs".to_owned()];
+        let report = Report {
+            files: &files,
+            n_docs: 1,
+            n_cached_docs: 0,
+            findings: &[],
+            stale_entries: &[],
+            group_by: None,
+        };
+
+        assert_eq!(
+            Summary::from(&report).to_string(),
+            "Checked 1 files and 1 docs (0 served from the cache).\nNo findings."
+        );
+    }
+}
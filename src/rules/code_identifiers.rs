@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use super::Rule;
+use crate::doc::Doc;
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::LocalMatch;
+use crate::markdown::prose_tokens;
+
+/// Rule id of the code identifiers written as prose.
+pub const CODE_IDENTIFIER_RULE_ID: &str = "CODE_IDENTIFIER";
+
+/// Finds the words which look like code outside backticks, e.g. `get_or`,
+/// `cache::Cacheable`, `check()`, or the `CamelCase` name of an item.
+pub struct CodeIdentifiers {
+    item_names: HashSet<String>,
+}
+
+impl CodeIdentifiers {
+    /// `item_names` are the names of the items of the crate.
+    #[must_use]
+    pub const fn new(item_names: HashSet<String>) -> Self {
+        Self { item_names }
+    }
+
+    /// Returns true if the word looks like code.
+    fn is_code(&self, word: &str) -> bool {
+        let is_identifier = |word: &str| {
+            !word.is_empty()
+                && !word.starts_with(|chr: char| chr.is_ascii_digit())
+                && word
+                    .chars()
+                    .all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+        };
+
+        // e.g. `get_or` or `MAX_SUGGESTIONS`
+        let is_snake_case = |word: &str| {
+            is_identifier(word)
+                && word.contains(|chr: char| chr.is_ascii_alphabetic())
+                && word
+                    .trim_matches('_')
+                    .split('_')
+                    .filter(|part| !part.is_empty())
+                    .count()
+                    > 1
+        };
+
+        // e.g. `CheckRequest`
+        let is_camel_case = |word: &str| {
+            is_identifier(word)
+                && word.starts_with(|chr: char| chr.is_ascii_uppercase())
+                && word.chars().skip(1).any(|chr| chr.is_ascii_uppercase())
+                && word.chars().any(|chr| chr.is_ascii_lowercase())
+        };
+
+        if let Some(name) = word.strip_suffix("()") {
+            return is_identifier(name) || self.is_code(name);
+        }
+
+        // e.g. `cache::Cacheable` or `::std::fmt`
+        if word.contains("::") {
+            let path = word.strip_prefix("::").unwrap_or(word);
+            return path.contains("::") && path.split("::").all(is_identifier);
+        }
+
+        is_snake_case(word) || (is_camel_case(word) && self.item_names.contains(word))
+    }
+}
+
+/// Strips the punctuation around a token, and returns the offset of the word
+/// in the token.
+fn strip_punctuation(token: &str) -> (usize, &str) {
+    let word = token.trim_start_matches(['(', '"', '\'', '*']);
+    let skipped = token.chars().count() - word.chars().count();
+
+    let mut word = word.trim_end_matches(['.', ',', ';', ':', '!', '?', '"', '\'', '*']);
+    while word.ends_with(')') && !word.ends_with("()") {
+        word = &word[..word.len() - 1];
+    }
+
+    (skipped, word)
+}
+
+impl Rule for CodeIdentifiers {
+    fn id(&self) -> &'static str {
+        CODE_IDENTIFIER_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Code
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        prose_tokens(&doc.to_string(), "")
+            .into_iter()
+            // links, URLs and markup are left as is.
+            .filter(|(_, token)| !token.contains(['[', ']', '<', '>', '/', '@']))
+            .filter_map(|(offset, token)| {
+                let (skipped, word) = strip_punctuation(token);
+                self.is_code(word).then(|| LocalMatch {
+                    offset: offset + skipped,
+                    length: word.chars().count(),
+                    message: "Code identifiers should be written in backticks.".to_owned(),
+                    short_message: "Code in prose".to_owned(),
+                    replacements: vec![format!("`{word}`")],
+                    rule_id: CODE_IDENTIFIER_RULE_ID.to_owned(),
+                    rule_description: "Code identifier outside backticks".to_owned(),
+                    issue_type: "style".to_owned(),
+                    category_id: self.category().to_string(),
+                    category_name: "Code".to_owned(),
+                })
+            })
+            .collect()
+    }
+
+    fn covers_misspellings(&self) -> bool {
+        // identifiers are not words of the language.
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::tests::docs;
+
+    fn rule() -> CodeIdentifiers {
+        CodeIdentifiers::new(HashSet::from(["CheckRequest".to_owned()]))
+    }
+
+    #[test]
+    fn code() {
+        let rule = rule();
+
+        for word in [
+            "get_or",
+            "MAX_SUGGESTIONS",
+            "check()",
+            "cache::Cacheable",
+            "::std::fmt",
+            "Cacheable::key()",
+            "CheckRequest",
+        ] {
+            assert!(rule.is_code(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn prose() {
+        let rule = rule();
+
+        for word in [
+            "word",
+            "::",
+            "Word::",
+            "::Word",
+            "a::::b",
+            "_",
+            "1_000",
+            "(s)",
+            "iPhone",
+            "McDonald",
+            "JavaScript",
+            "()",
+        ] {
+            assert!(!rule.is_code(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn check() {
+        let source = "/// Calls get_or (and `get_mut`), then check().\n///\n/// ```\n/// max_len\n/// ```\n/// Ünïcödé cache::Cacheable.\nfn f() {}\n";
+        let docs = docs(source);
+        let doc = &docs[0];
+        let text = doc.to_string();
+
+        let matches: Vec<_> = rule()
+            .check(doc)
+            .into_iter()
+            .map(|found| {
+                let word: String = text.chars().skip(found.offset).take(found.length).collect();
+                (word, found.replacements)
+            })
+            .collect();
+
+        assert_eq!(
+            matches,
+            vec![
+                ("get_or".to_owned(), vec!["`get_or`".to_owned()]),
+                ("check()".to_owned(), vec!["`check()`".to_owned()]),
+                (
+                    "cache::Cacheable".to_owned(),
+                    vec!["`cache::Cacheable`".to_owned()]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,597 @@
+//! Optional rules for the conventions of rustdoc, which `languagetool` does not know.
+//!
+//! The summary of a doc is its first paragraph, shown in the item lists of rustdoc.
+
+use super::Rule;
+use crate::doc::Doc;
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::LocalMatch;
+use crate::markdown::code_chars;
+
+/// Rule id of the summaries which are not a single sentence ending in a period.
+pub const SUMMARY_SENTENCE_RULE_ID: &str = "DOC_SUMMARY_SENTENCE";
+
+/// Rule id of the function summaries starting with a verb in the imperative.
+pub const SUMMARY_VERB_RULE_ID: &str = "DOC_SUMMARY_VERB";
+
+/// Rule id of the summaries over the length limit.
+pub const SUMMARY_LENGTH_RULE_ID: &str = "DOC_SUMMARY_LENGTH";
+
+/// Rule id of the doc lines with trailing whitespace.
+pub const TRAILING_WHITESPACE_RULE_ID: &str = "DOC_TRAILING_WHITESPACE";
+
+/// Rule id of the doubled blank lines in docs.
+pub const BLANK_LINES_RULE_ID: &str = "DOC_BLANK_LINES";
+
+/// Length limit of the summaries, in chars.
+pub const DEFAULT_MAX_SUMMARY_LENGTH: usize = 120;
+
+/// Verbs which start function summaries, in the imperative.
+const VERBS: &[&str] = &[
+    "accept",
+    "add",
+    "allocate",
+    "append",
+    "apply",
+    "build",
+    "calculate",
+    "call",
+    "cancel",
+    "check",
+    "clear",
+    "clone",
+    "close",
+    "collect",
+    "compare",
+    "compute",
+    "connect",
+    "construct",
+    "consume",
+    "convert",
+    "copy",
+    "count",
+    "create",
+    "decode",
+    "delete",
+    "deserialize",
+    "determine",
+    "do",
+    "drop",
+    "emit",
+    "encode",
+    "ensure",
+    "evaluate",
+    "execute",
+    "extend",
+    "extract",
+    "fetch",
+    "fill",
+    "filter",
+    "find",
+    "finish",
+    "flush",
+    "format",
+    "generate",
+    "get",
+    "handle",
+    "initialize",
+    "insert",
+    "iterate",
+    "join",
+    "load",
+    "lock",
+    "look",
+    "make",
+    "map",
+    "merge",
+    "move",
+    "open",
+    "parse",
+    "perform",
+    "pop",
+    "print",
+    "process",
+    "push",
+    "read",
+    "receive",
+    "register",
+    "remove",
+    "render",
+    "replace",
+    "reset",
+    "resolve",
+    "return",
+    "run",
+    "save",
+    "search",
+    "send",
+    "serialize",
+    "set",
+    "sort",
+    "spawn",
+    "split",
+    "start",
+    "stop",
+    "store",
+    "take",
+    "transform",
+    "try",
+    "update",
+    "validate",
+    "visit",
+    "wait",
+    "walk",
+    "wrap",
+    "write",
+];
+
+/// Abbreviations which end with a period inside a sentence.
+const ABBREVIATIONS: &[&str] = &["e.g", "i.e", "etc", "vs", "cf", "approx"];
+
+/// Builds a match of a rustdoc convention.
+fn convention_match(
+    rule_id: &str,
+    offset: usize,
+    length: usize,
+    message: String,
+    replacements: Vec<String>,
+) -> LocalMatch {
+    let (description, issue_type) = match rule_id {
+        SUMMARY_SENTENCE_RULE_ID => ("Summary is not a single sentence", "style"),
+        SUMMARY_VERB_RULE_ID => ("Summary verb in the imperative", "style"),
+        SUMMARY_LENGTH_RULE_ID => ("Summary is too long", "style"),
+        TRAILING_WHITESPACE_RULE_ID => ("Trailing whitespace", "whitespace"),
+        _ => ("Doubled blank line", "whitespace"),
+    };
+
+    LocalMatch {
+        offset,
+        length,
+        message,
+        short_message: description.to_owned(),
+        replacements,
+        rule_id: rule_id.to_owned(),
+        rule_description: description.to_owned(),
+        issue_type: issue_type.to_owned(),
+        category_id: Categories::Docs.to_string(),
+        category_name: "Documentation".to_owned(),
+    }
+}
+
+/// Returns the char range of the summary of a doc, without the trailing
+/// whitespace, if the doc starts with prose.
+fn summary(text: &str) -> Option<(usize, usize)> {
+    let first_line = text.lines().next()?.trim_start();
+
+    let is_prose = !first_line.is_empty()
+        && !["#", "```", "~~~", "<", "|", "!", "- ", "* ", "+ ", ">"]
+            .iter()
+            .any(|marker| first_line.starts_with(marker));
+    if !is_prose {
+        return None;
+    }
+
+    let paragraph: Vec<&str> = text
+        .split('\n')
+        .take_while(|line| {
+            let line = line.trim_start();
+            !line.is_empty() && !line.starts_with("```") && !line.starts_with("~~~")
+        })
+        .collect();
+    let paragraph = paragraph.join("\n");
+
+    Some((0, paragraph.trim_end().chars().count()))
+}
+
+/// Returns the char offsets of the sentences which follow the first one in the
+/// summary.
+fn next_sentences(summary: &[char], code: &[bool]) -> Vec<usize> {
+    summary
+        .windows(3)
+        .enumerate()
+        .filter(|(index, window)| {
+            matches!(window[0], '.' | '!' | '?')
+                && !code[*index]
+                && window[1].is_whitespace()
+                && window[2].is_uppercase()
+        })
+        .filter(|(index, _)| {
+            let word: String = summary[..*index]
+                .iter()
+                .rev()
+                .take_while(|chr| !chr.is_whitespace())
+                .collect::<String>()
+                .chars()
+                .rev()
+                .collect();
+            let word = word.trim_start_matches(['(', '"', '\'']).to_lowercase();
+            !ABBREVIATIONS.contains(&word.as_str())
+        })
+        .map(|(index, _)| index + 2)
+        .collect()
+}
+
+/// The summary must be a single sentence ending in a period.
+pub struct SummarySentence;
+
+impl Rule for SummarySentence {
+    fn id(&self) -> &'static str {
+        SUMMARY_SENTENCE_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Docs
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let text = doc.to_string();
+        let Some((start, end)) = summary(&text) else {
+            return vec![];
+        };
+
+        let chars: Vec<char> = text.chars().collect();
+        let code = code_chars(&text);
+        let summary = &chars[start..end];
+
+        if let Some(&next) = next_sentences(summary, &code[start..end]).first() {
+            return vec![convention_match(
+                SUMMARY_SENTENCE_RULE_ID,
+                start + next,
+                summary.len() - next,
+                "The summary should be a single sentence; move the rest to a new paragraph."
+                    .to_owned(),
+                vec![],
+            )];
+        }
+
+        match summary.last() {
+            Some('.') | None => vec![],
+            Some(&last) => {
+                let replacements = if last.is_alphanumeric() || matches!(last, ')' | '`' | '"') {
+                    vec![format!("{last}.")]
+                } else {
+                    vec![]
+                };
+                vec![convention_match(
+                    SUMMARY_SENTENCE_RULE_ID,
+                    end - 1,
+                    1,
+                    "The summary should end in a period.".to_owned(),
+                    replacements,
+                )]
+            }
+        }
+    }
+}
+
+/// Returns the verb in the third person, e.g. `Returns` for `Return`.
+fn third_person(verb: &str) -> String {
+    if let Some(stem) = verb.strip_suffix('y') {
+        if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) {
+            return format!("{stem}ies");
+        }
+    }
+
+    if ["s", "sh", "ch", "x", "z", "o"]
+        .iter()
+        .any(|suffix| verb.ends_with(suffix))
+    {
+        format!("{verb}es")
+    } else {
+        format!("{verb}s")
+    }
+}
+
+/// Function summaries must start with a verb in the third person, e.g.
+/// `Returns` rather than `Return`.
+pub struct SummaryVerb;
+
+impl Rule for SummaryVerb {
+    fn id(&self) -> &'static str {
+        SUMMARY_VERB_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Docs
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let text = doc.to_string();
+        if !doc.is_fn || summary(&text).is_none() {
+            return vec![];
+        }
+
+        let first_word: String = text
+            .trim_start()
+            .chars()
+            .take_while(|chr| chr.is_alphabetic())
+            .collect();
+
+        if !first_word.starts_with(char::is_uppercase)
+            || !VERBS.contains(&first_word.to_lowercase().as_str())
+        {
+            return vec![];
+        }
+
+        let offset = text.chars().count() - text.trim_start().chars().count();
+
+        vec![convention_match(
+            SUMMARY_VERB_RULE_ID,
+            offset,
+            first_word.chars().count(),
+            "Function summaries should start with a verb in the third person.".to_owned(),
+            vec![third_person(&first_word)],
+        )]
+    }
+}
+
+/// The summary must be shorter than a length limit.
+pub struct SummaryLength {
+    max_length: usize,
+}
+
+impl SummaryLength {
+    /// `max_length` is the length limit, in chars.
+    #[must_use]
+    pub const fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Rule for SummaryLength {
+    fn id(&self) -> &'static str {
+        SUMMARY_LENGTH_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Docs
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let text = doc.to_string();
+        match summary(&text) {
+            Some((start, end)) if end - start > self.max_length => {
+                vec![convention_match(
+                    SUMMARY_LENGTH_RULE_ID,
+                    start + self.max_length,
+                    end - start - self.max_length,
+                    format!(
+                        "The summary is {} chars long, over the limit of {}.",
+                        end - start,
+                        self.max_length
+                    ),
+                    vec![],
+                )]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Doc lines must not end with whitespace.
+pub struct TrailingWhitespace;
+
+impl Rule for TrailingWhitespace {
+    fn id(&self) -> &'static str {
+        TRAILING_WHITESPACE_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Docs
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let mut matches = vec![];
+        let mut line_offset = 0;
+
+        for (line, _) in &doc.text {
+            let length = line.chars().count();
+            let trimmed_length = line.trim_end().chars().count();
+
+            if trimmed_length < length {
+                matches.push(convention_match(
+                    TRAILING_WHITESPACE_RULE_ID,
+                    line_offset + trimmed_length,
+                    length - trimmed_length,
+                    "Remove the trailing whitespace.".to_owned(),
+                    vec![String::new()],
+                ));
+            }
+
+            line_offset += length + 1; // because of newline
+        }
+
+        matches
+    }
+}
+
+/// Docs must not have several blank lines in a row, outside code blocks.
+pub struct BlankLines;
+
+impl Rule for BlankLines {
+    fn id(&self) -> &'static str {
+        BLANK_LINES_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Docs
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let text = doc.to_string();
+        let code = code_chars(&text);
+        let mut matches = vec![];
+        let mut line_offset = 0;
+        let mut previous_is_blank = false;
+
+        for (line, _) in &doc.text {
+            let is_blank = line.trim().is_empty();
+
+            // the newline which ends the previous blank line.
+            if is_blank && previous_is_blank && !code[line_offset - 1] {
+                matches.push(convention_match(
+                    BLANK_LINES_RULE_ID,
+                    line_offset - 1,
+                    1,
+                    "Remove the doubled blank line.".to_owned(),
+                    vec![String::new()],
+                ));
+            }
+
+            previous_is_blank = is_blank;
+            line_offset += line.chars().count() + 1; // because of newline
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::tests::docs;
+
+    /// Returns the matched text and the replacements of the matches of a rule
+    /// in the docs of a source.
+    fn matches(rule: &dyn Rule, source: &str) -> Vec<(String, Vec<String>)> {
+        docs(source)
+            .iter()
+            .flat_map(|doc| {
+                let text = doc.to_string();
+                rule.check(doc).into_iter().map(move |found| {
+                    let matched = text.chars().skip(found.offset).take(found.length).collect();
+                    (matched, found.replacements)
+                })
+            })
+            .collect()
+    }
+
+    fn next_sentences_of(text: &str) -> Vec<usize> {
+        let chars: Vec<char> = text.chars().collect();
+        next_sentences(&chars, &code_chars(text))
+    }
+
+    #[test]
+    fn summaries() {
+        assert_eq!(
+            summary("Returns the value.\nMore.\n\nDetails."),
+            Some((0, 24))
+        );
+        assert_eq!(
+            summary("Returns the välue.  \n```\ncode\n```"),
+            Some((0, 18))
+        );
+        assert_eq!(summary("# Examples\n\nText."), None);
+        assert_eq!(summary("- item"), None);
+        assert_eq!(summary(""), None);
+    }
+
+    #[test]
+    fn sentences() {
+        assert_eq!(next_sentences_of("Returns the value. Then more."), vec![19]);
+        assert_eq!(
+            next_sentences_of("Returns e.g. Values, i.e. Keys."),
+            Vec::<usize>::new()
+        );
+        assert_eq!(next_sentences_of("Calls `a. B` now."), Vec::<usize>::new());
+        assert_eq!(
+            next_sentences_of("Returns 1.5 times the value."),
+            Vec::<usize>::new()
+        );
+        assert_eq!(next_sentences_of("Ünïcödé! Then more."), vec![9]);
+    }
+
+    #[test]
+    fn verbs_in_the_third_person() {
+        for (verb, expected) in [
+            ("Return", "Returns"),
+            ("Try", "Tries"),
+            ("Say", "Says"),
+            ("Push", "Pushes"),
+            ("Fix", "Fixes"),
+            ("Match", "Matches"),
+            ("Do", "Does"),
+        ] {
+            assert_eq!(third_person(verb), expected);
+        }
+    }
+
+    #[test]
+    fn summary_sentence() {
+        let source = "/// Returns the value. Then more.\nfn f() {}\n/// Returns the value\nfn g() {}\n/// Returns the value.\nfn h() {}\n";
+
+        assert_eq!(
+            matches(&SummarySentence, source),
+            vec![
+                ("Then more.".to_owned(), vec![]),
+                ("e".to_owned(), vec!["e.".to_owned()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_verb() {
+        let source = "/// Return the value.\nfn f() {}\n/// Returns the value.\nfn g() {}\n/// Return value.\nstruct S;\n";
+
+        assert_eq!(
+            matches(&SummaryVerb, source),
+            vec![("Return".to_owned(), vec!["Returns".to_owned()])]
+        );
+    }
+
+    #[test]
+    fn summary_length() {
+        let source = "/// Returns the välue.\nfn f() {}\n";
+
+        assert_eq!(
+            matches(&SummaryLength::new(12), source),
+            vec![("välue.".to_owned(), vec![])]
+        );
+        assert!(matches(&SummaryLength::new(18), source).is_empty());
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let source = "/// Ünïcödé  \n/// text\nfn f() {}\n";
+
+        assert_eq!(
+            matches(&TrailingWhitespace, source),
+            vec![("  ".to_owned(), vec![String::new()])]
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let source = "/// Ünïcödé.\n///\n///\n/// Text.\n///\n/// ```\n/// a\n///\n///\n/// b\n/// ```\nfn f() {}\n";
+
+        assert_eq!(
+            matches(&BlankLines, source),
+            vec![("\n".to_owned(), vec![String::new()])]
+        );
+
+        let docs = docs(source);
+        let found = BlankLines.check(&docs[0]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].offset, 9);
+    }
+}
@@ -0,0 +1,324 @@
+//! The project glossary: the preferred terms, and their banned variants.
+
+use std::path::Path;
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use serde::Deserialize;
+
+use super::Rule;
+use crate::cli::Config;
+use crate::doc::Doc;
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::LocalMatch;
+use crate::markdown::code_chars;
+
+/// The project glossary, if the file exists.
+pub const GLOSSARY_FILE: &str = "languagetool-glossary.toml";
+
+/// Rule id of the glossary, to enable or disable all its terms.
+pub const GLOSSARY_RULE_ID: &str = "GLOSSARY";
+
+/// A preferred term.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Term {
+    pub preferred: String,
+    /// The variants reported, matched as whole words and case-sensitively.
+    pub banned: Vec<String>,
+    /// Rule id of the term, starting with `GLOSSARY_`. [default: `GLOSSARY_`
+    /// and the preferred term in uppercase, e.g. `GLOSSARY_GITHUB`]
+    pub id: Option<String>,
+}
+
+impl Term {
+    /// The rule id of the term.
+    #[must_use]
+    pub fn rule_id(&self) -> String {
+        self.id.clone().unwrap_or_else(|| {
+            let name: String = self
+                .preferred
+                .chars()
+                .map(|chr| {
+                    if chr.is_alphanumeric() {
+                        chr.to_ascii_uppercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{GLOSSARY_RULE_ID}_{name}")
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Glossary {
+    #[serde(default, rename = "term")]
+    pub terms: Vec<Term>,
+}
+
+impl Glossary {
+    /// Reads a glossary. A missing file is an empty glossary.
+    ///
+    /// # Errors
+    /// If the file exists but cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        toml::from_str::<Self>(&std::fs::read_to_string(path)?)?.validated()
+    }
+
+    /// Checks the rule ids of the terms, which must be known as local rules.
+    ///
+    /// # Errors
+    /// If a rule id does not start with `GLOSSARY_`.
+    pub fn validated(self) -> Result<Self> {
+        for term in &self.terms {
+            let rule_id = term.rule_id();
+            if !rule_id.starts_with(&format!("{GLOSSARY_RULE_ID}_")) {
+                bail!(
+                    "the id of the glossary term \"{}\" must start with {GLOSSARY_RULE_ID}_: {rule_id}",
+                    term.preferred
+                );
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Keeps the terms enabled by the configuration, by their own rule id or
+    /// by `GLOSSARY` and the `TERMINOLOGY` category.
+    #[must_use]
+    pub fn enabled(mut self, config: &Config) -> Self {
+        let is_disabled = |rule_id: &str| config.disable_rules.iter().any(|id| id == rule_id);
+        let is_enabled =
+            |rule_id: &str| config.is_local_rule_enabled(rule_id, &Categories::Terminology);
+
+        self.terms.retain(|term| {
+            let rule_id = term.rule_id();
+            !is_disabled(GLOSSARY_RULE_ID)
+                && (is_enabled(GLOSSARY_RULE_ID) || is_enabled(&rule_id))
+                && !is_disabled(&rule_id)
+        });
+        self
+    }
+}
+
+/// Returns true if the char is part of a word, a path or a URL, e.g. `github`
+/// in `github.com` or `rust-lang/rust`.
+fn is_word_char(chr: char) -> bool {
+    chr.is_alphanumeric() || matches!(chr, '_' | '-' | '/' | ':' | '@')
+}
+
+/// Returns the char offsets of the whole word occurrences of a variant in the
+/// prose of the text.
+fn occurrences(text: &str, code: &[bool], variant: &str) -> Vec<usize> {
+    let length = variant.chars().count();
+
+    text.match_indices(variant)
+        .filter(|(byte_index, _)| {
+            let before = text[..*byte_index].chars().next_back();
+            let mut after = text[byte_index + variant.len()..].chars();
+            let next = after.next();
+
+            let is_continued = next.is_some_and(is_word_char)
+                // e.g. `github.com`
+                || (next == Some('.') && after.next().is_some_and(char::is_alphanumeric));
+
+            !before.is_some_and(|chr| is_word_char(chr) || chr == '.') && !is_continued
+        })
+        .map(|(byte_index, _)| text[..byte_index].chars().count())
+        .filter(|offset| !code[*offset..offset + length].contains(&true))
+        .collect()
+}
+
+impl Rule for Glossary {
+    fn id(&self) -> &'static str {
+        GLOSSARY_RULE_ID
+    }
+
+    fn category(&self) -> Categories {
+        Categories::Terminology
+    }
+
+    fn is_enabled(&self, _config: &Config) -> bool {
+        // the terms are enabled one by one.
+        !self.terms.is_empty()
+    }
+
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch> {
+        let text = doc.to_string();
+        let code = code_chars(&text);
+
+        self.terms
+            .iter()
+            .flat_map(|term| {
+                let (text, code) = (&text, &code);
+                term.banned
+                    .iter()
+                    .filter(|variant| !variant.is_empty())
+                    .flat_map(move |variant| {
+                        occurrences(text, code, variant)
+                            .into_iter()
+                            .map(move |offset| LocalMatch {
+                                offset,
+                                length: variant.chars().count(),
+                                message: format!(
+                                    "Use \"{}\" instead of \"{variant}\".",
+                                    term.preferred
+                                ),
+                                short_message: "Glossary term".to_owned(),
+                                replacements: vec![term.preferred.clone()],
+                                rule_id: term.rule_id(),
+                                rule_description: format!("Preferred term: {}", term.preferred),
+                                issue_type: "terminology".to_owned(),
+                                category_id: Categories::Terminology.to_string(),
+                                category_name: "Terminology".to_owned(),
+                            })
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn occurrences_in_prose(text: &str, variant: &str) -> Vec<usize> {
+        occurrences(text, &code_chars(text), variant)
+    }
+
+    fn glossary() -> Glossary {
+        toml::from_str(
+            r#"
+            [[term]]
+            preferred = "GitHub"
+            banned = ["Github"]
+
+            [[term]]
+            preferred = "Rust"
+            banned = ["rust"]
+            id = "GLOSSARY_RUST_CASING"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn enabled_rule_ids(args: &[&str]) -> Vec<String> {
+        let config = Config::parse_from([&["languagetool"], args].concat());
+
+        glossary()
+            .enabled(&config)
+            .terms
+            .iter()
+            .map(Term::rule_id)
+            .collect()
+    }
+
+    #[test]
+    fn whole_words() {
+        assert_eq!(
+            occurrences_in_prose("Github and Github.", "Github"),
+            vec![0, 11]
+        );
+        assert_eq!(
+            occurrences_in_prose("Githubs", "Github"),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            occurrences_in_prose("MyGithub", "Github"),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn paths_and_urls() {
+        for text in [
+            "github.com",
+            "www.github",
+            "rust-lang/github",
+            "https://github",
+            "user@github",
+            "github_token",
+        ] {
+            assert_eq!(
+                occurrences_in_prose(text, "github"),
+                Vec::<usize>::new(),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            occurrences_in_prose("`github` and github", "github"),
+            vec![13]
+        );
+        assert_eq!(
+            occurrences_in_prose("```\ngithub\n```\ngithub", "github"),
+            vec![15]
+        );
+    }
+
+    #[test]
+    fn char_offsets() {
+        assert_eq!(occurrences_in_prose("Ünïcödé, Github", "Github"), vec![9]);
+    }
+
+    #[test]
+    fn rule_ids() {
+        let glossary = glossary();
+
+        assert_eq!(glossary.terms[0].rule_id(), "GLOSSARY_GITHUB");
+        assert_eq!(glossary.terms[1].rule_id(), "GLOSSARY_RUST_CASING");
+        assert!(glossary.validated().is_ok());
+
+        let invalid: Glossary = toml::from_str(
+            r#"
+            [[term]]
+            preferred = "Rust"
+            banned = ["rust"]
+            id = "RUST"
+            "#,
+        )
+        .unwrap();
+        assert!(invalid.validated().is_err());
+    }
+
+    #[test]
+    fn enabled_terms() {
+        let all = vec!["GLOSSARY_GITHUB", "GLOSSARY_RUST_CASING"];
+
+        assert_eq!(enabled_rule_ids(&[]), all);
+        assert_eq!(
+            enabled_rule_ids(&["--disable-rules", "GLOSSARY_GITHUB"]),
+            vec!["GLOSSARY_RUST_CASING"]
+        );
+        assert!(enabled_rule_ids(&["--disable-rules", "GLOSSARY"]).is_empty());
+        assert!(enabled_rule_ids(&["--disable-categories", "TERMINOLOGY"]).is_empty());
+        assert!(
+            enabled_rule_ids(&["--enable-only", "--enable-rules", "CODE_IDENTIFIER"]).is_empty()
+        );
+        assert_eq!(
+            enabled_rule_ids(&["--enable-only", "--enable-rules", "GLOSSARY_RUST_CASING"]),
+            vec!["GLOSSARY_RUST_CASING"]
+        );
+        assert_eq!(
+            enabled_rule_ids(&["--enable-only", "--enable-rules", "GLOSSARY"]),
+            all
+        );
+        assert_eq!(
+            enabled_rule_ids(&["--enable-only", "--enable-categories", "TERMINOLOGY"]),
+            all
+        );
+    }
+}
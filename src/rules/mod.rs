@@ -0,0 +1,186 @@
+//! The `rules` module contains the rules which are checked locally on the text of the docs,
+//! alongside the results of `languagetool`.
+//!
+//! Their matches go through the same suppression, reporting and fixing as the matches of
+//! `languagetool`.
+
+pub mod code_identifiers;
+pub mod conventions;
+pub mod glossary;
+
+use std::collections::HashSet;
+
+use crate::cli::Config;
+use crate::doc::Doc;
+use crate::languagetool::categories::Categories;
+use crate::languagetool::local::LocalMatch;
+use glossary::Glossary;
+
+/// A rule checked locally on the text of a doc.
+pub trait Rule {
+    /// The id of the rule.
+    fn id(&self) -> &'static str;
+
+    /// The category of the rule.
+    fn category(&self) -> Categories;
+
+    /// Returns true if the rule is enabled by the configuration.
+    fn is_enabled(&self, config: &Config) -> bool {
+        config.is_local_rule_enabled(self.id(), &self.category())
+    }
+
+    /// Returns the matches of the rule in the text of a doc. Offsets are in chars.
+    fn check(&self, doc: &Doc) -> Vec<LocalMatch>;
+
+    /// Whether the rule only runs when enabled with `--enable-rules`, with its
+    /// id or its category id.
+    fn is_optional(&self) -> bool {
+        false
+    }
+
+    /// Whether the misspellings which overlap a match are dropped, e.g.
+    /// because the match is not a word.
+    fn covers_misspellings(&self) -> bool {
+        false
+    }
+}
+
+/// Returns true if the rule is a local rule, unknown to `languagetool`.
+#[must_use]
+pub fn is_local_rule(rule_id: &str) -> bool {
+    rule_id == code_identifiers::CODE_IDENTIFIER_RULE_ID
+        || rule_id == glossary::GLOSSARY_RULE_ID
+        || rule_id.starts_with(&format!("{}_", glossary::GLOSSARY_RULE_ID))
+        || [
+            conventions::SUMMARY_SENTENCE_RULE_ID,
+            conventions::SUMMARY_VERB_RULE_ID,
+            conventions::SUMMARY_LENGTH_RULE_ID,
+            conventions::TRAILING_WHITESPACE_RULE_ID,
+            conventions::BLANK_LINES_RULE_ID,
+        ]
+        .contains(&rule_id)
+}
+
+/// The enabled local rules.
+pub struct Rules(Vec<Box<dyn Rule>>);
+
+impl Rules {
+    /// The local rules enabled by the configuration.
+    ///
+    /// `item_names` are the names of the items of the crate, and `glossary` the
+    /// preferred terms of the project.
+    #[must_use]
+    pub fn new(config: &Config, item_names: HashSet<String>, glossary: Glossary) -> Self {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(code_identifiers::CodeIdentifiers::new(item_names)),
+            Box::new(glossary.enabled(config)),
+            Box::new(conventions::SummarySentence),
+            Box::new(conventions::SummaryVerb),
+            Box::new(conventions::SummaryLength::new(config.max_summary_length())),
+            Box::new(conventions::TrailingWhitespace),
+            Box::new(conventions::BlankLines),
+        ];
+
+        Self(
+            rules
+                .into_iter()
+                .filter(|rule| rule.is_enabled(config))
+                .filter(|rule| {
+                    !rule.is_optional()
+                        || config.doc_conventions
+                        || config.enable_rules.iter().any(|id| id == rule.id())
+                        || config.enable_categories.contains(&rule.category())
+                })
+                .collect(),
+        )
+    }
+
+    /// Iterates over the rules.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.0.iter().map(AsRef::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn rule_ids(args: &[&str]) -> Vec<&'static str> {
+        let config = Config::parse_from([&["languagetool"], args].concat());
+
+        Rules::new(&config, HashSet::new(), Glossary::default())
+            .iter()
+            .map(Rule::id)
+            .collect()
+    }
+
+    #[test]
+    fn local_rules() {
+        assert!(is_local_rule("CODE_IDENTIFIER"));
+        assert!(is_local_rule("GLOSSARY"));
+        assert!(is_local_rule("GLOSSARY_GITHUB"));
+        assert!(is_local_rule("DOC_BLANK_LINES"));
+        assert!(!is_local_rule("MORFOLOGIK_RULE_EN_US"));
+    }
+
+    #[test]
+    fn enabled_rules() {
+        assert!(rule_ids(&[]).contains(&"CODE_IDENTIFIER"));
+        assert!(!rule_ids(&["--disable-rules", "CODE_IDENTIFIER"]).contains(&"CODE_IDENTIFIER"));
+        assert!(!rule_ids(&["--disable-categories", "CODE"]).contains(&"CODE_IDENTIFIER"));
+        assert!(!rule_ids(&[
+            "--enable-only",
+            "--enable-rules",
+            "UPPERCASE_SENTENCE_START"
+        ])
+        .contains(&"CODE_IDENTIFIER"));
+        assert!(rule_ids(&["--enable-only", "--enable-categories", "CODE"])
+            .contains(&"CODE_IDENTIFIER"));
+    }
+
+    #[test]
+    fn doc_conventions() {
+        let docs = [
+            "DOC_SUMMARY_SENTENCE",
+            "DOC_SUMMARY_VERB",
+            "DOC_SUMMARY_LENGTH",
+            "DOC_TRAILING_WHITESPACE",
+            "DOC_BLANK_LINES",
+        ];
+        let is_doc_rule = |id: &&str| id.starts_with("DOC_");
+
+        assert!(rule_ids(&[]).iter().all(|id| !is_doc_rule(id)));
+        assert_eq!(
+            rule_ids(&["--doc-conventions"])
+                .into_iter()
+                .filter(is_doc_rule)
+                .collect::<Vec<_>>(),
+            docs
+        );
+        assert_eq!(
+            rule_ids(&["--enable-categories", "DOCS"])
+                .into_iter()
+                .filter(is_doc_rule)
+                .collect::<Vec<_>>(),
+            docs
+        );
+        assert_eq!(
+            rule_ids(&["--enable-rules", "DOC_BLANK_LINES"])
+                .into_iter()
+                .filter(is_doc_rule)
+                .collect::<Vec<_>>(),
+            vec!["DOC_BLANK_LINES"]
+        );
+        assert_eq!(
+            rule_ids(&["--enable-only", "--enable-categories", "DOCS"]),
+            docs
+        );
+        assert!(
+            rule_ids(&["--doc-conventions", "--disable-categories", "DOCS"])
+                .iter()
+                .all(|id| !is_doc_rule(id))
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Suppression of findings with directives in the source.
+//!
+//! - `// languagetool:disable-next-line RULE_ID` suppresses the findings on the next line.
+//! - `<!-- languagetool-disable RULE_ID -->` in a doc comment suppresses the findings until
+//!   `<!-- languagetool-enable -->` or the end of the doc comment.
+//!
+//! The rule ids are optional; without them, all findings are suppressed. Category ids can be
+//! used in place of rule ids.
+
+use core::ops::RangeInclusive;
+
+const DISABLE_NEXT_LINE: &str = "languagetool:disable-next-line";
+const DISABLE: &str = "languagetool-disable";
+const ENABLE: &str = "languagetool-enable";
+
+#[derive(Debug, Clone)]
+struct Suppression {
+    lines: RangeInclusive<usize>,
+    /// Suppressed rule or category ids. All findings are suppressed if empty.
+    ids: Vec<String>,
+}
+
+/// Suppressions of a source file.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions(Vec<Suppression>);
+
+/// Returns the ids following a directive, if the line contains it.
+fn directive_ids(line: &str, directive: &str) -> Option<Vec<String>> {
+    let (_, rest) = line.split_once(directive)?;
+
+    // `languagetool-disable` must not match `languagetool-disable-next-line` and alike.
+    if rest.starts_with(|chr: char| chr.is_alphanumeric() || chr == '-' || chr == '_') {
+        return None;
+    }
+
+    let rest = rest.split("-->").next().unwrap_or(rest);
+    let rest = rest.split("*/").next().unwrap_or(rest);
+
+    Some(
+        rest.split([' ', ','])
+            .filter(|id| !id.is_empty())
+            .map(ToOwned::to_owned)
+            .collect(),
+    )
+}
+
+/// Returns true if the line is a doc comment.
+fn is_doc_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("///") || line.starts_with("//!")
+}
+
+impl From<&str> for Suppressions {
+    fn from(source: &str) -> Self {
+        let mut suppressions = vec![];
+        let mut open = vec![];
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+
+            if !open.is_empty() && !is_doc_comment(line) {
+                close(&mut open, line_number - 1, &mut suppressions);
+            }
+
+            if let Some(ids) = directive_ids(line, DISABLE_NEXT_LINE) {
+                suppressions.push(Suppression {
+                    lines: line_number + 1..=line_number + 1,
+                    ids,
+                });
+            } else if let Some(ids) = directive_ids(line, DISABLE) {
+                open.push((line_number, ids));
+            } else if directive_ids(line, ENABLE).is_some() {
+                close(&mut open, line_number, &mut suppressions);
+            } else {
+                continue;
+            }
+
+            // the directive itself is not prose.
+            suppressions.push(Suppression {
+                lines: line_number..=line_number,
+                ids: vec![],
+            });
+        }
+
+        close(&mut open, source.lines().count(), &mut suppressions);
+
+        Self(suppressions)
+    }
+}
+
+/// Ends the open `languagetool-disable` ranges at the line.
+fn close(open: &mut Vec<(usize, Vec<String>)>, end: usize, suppressions: &mut Vec<Suppression>) {
+    suppressions.extend(open.drain(..).map(|(start, ids)| Suppression {
+        lines: start..=end,
+        ids,
+    }));
+}
+
+impl Suppressions {
+    /// Returns true if a finding of the rule or category on the line is suppressed.
+    #[must_use]
+    pub fn is_suppressed(&self, line: usize, rule_id: &str, category_id: &str) -> bool {
+        self.0.iter().any(|suppression| {
+            suppression.lines.contains(&line)
+                && (suppression.ids.is_empty()
+                    || suppression
+                        .ids
+                        .iter()
+                        .any(|id| id == rule_id || id == category_id))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids() {
+        assert_eq!(
+            directive_ids("// languagetool:disable-next-line A, B", DISABLE_NEXT_LINE),
+            Some(vec!["A".to_owned(), "B".to_owned()])
+        );
+        assert_eq!(
+            directive_ids("/// <!-- languagetool-disable A -->", DISABLE),
+            Some(vec!["A".to_owned()])
+        );
+        assert_eq!(
+            directive_ids("/* languagetool-disable */", DISABLE),
+            Some(vec![])
+        );
+        assert_eq!(
+            directive_ids("// languagetool:disable-next-line", DISABLE),
+            None
+        );
+        assert_eq!(
+            directive_ids("/// <!-- languagetool-disable-all -->", DISABLE),
+            None
+        );
+        assert_eq!(directive_ids("/// prose", DISABLE), None);
+    }
+
+    #[test]
+    fn next_line() {
+        let suppressions =
+            Suppressions::from("// languagetool:disable-next-line TYPOS\n/// teh\n/// teh\n");
+
+        assert!(suppressions.is_suppressed(1, "OTHER", "OTHER"));
+        assert!(suppressions.is_suppressed(2, "MORFOLOGIK_RULE_EN_US", "TYPOS"));
+        assert!(!suppressions.is_suppressed(2, "EN_QUOTES", "TYPOGRAPHY"));
+        assert!(!suppressions.is_suppressed(3, "MORFOLOGIK_RULE_EN_US", "TYPOS"));
+    }
+
+    #[test]
+    fn ranges() {
+        let suppressions = Suppressions::from(
+            "/// <!-- languagetool-disable RULE -->\n/// a\n/// <!-- languagetool-enable -->\n/// b\n",
+        );
+
+        assert!(suppressions.is_suppressed(2, "RULE", "TYPOS"));
+        assert!(!suppressions.is_suppressed(2, "OTHER", "TYPOS"));
+        assert!(suppressions.is_suppressed(3, "OTHER", "TYPOS"));
+        assert!(!suppressions.is_suppressed(4, "RULE", "TYPOS"));
+    }
+
+    #[test]
+    fn ranges_end_with_the_doc_comment() {
+        let suppressions =
+            Suppressions::from("/// <!-- languagetool-disable -->\n/// a\nfn f() {}\n/// b\n");
+
+        assert!(suppressions.is_suppressed(2, "RULE", "TYPOS"));
+        assert!(!suppressions.is_suppressed(4, "RULE", "TYPOS"));
+    }
+
+    #[test]
+    fn ranges_end_with_the_file() {
+        let suppressions = Suppressions::from("//! <!-- languagetool-disable -->\n//! a");
+
+        assert!(suppressions.is_suppressed(2, "RULE", "TYPOS"));
+    }
+}
@@ -1,11 +1,47 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use color_eyre::Result;
+use log::debug;
 use proc_macro2::TokenStream;
 
 use crate::cache::Cacheable;
+use crate::checker::Checker;
 use crate::cli::Config;
-use crate::doc::{Docs, RawDocs};
+use crate::config::LanguageMismatch;
+use crate::dictionary::Dictionary;
+use crate::doc::{Docs, RawDocs, LANGUAGE_MISMATCH_ID};
+use crate::finding::{Finding, Severity};
+use crate::rules::glossary::Glossary;
+use crate::rules::Rules;
+use crate::suppression::Suppressions;
+
+/// Returns the path of a file, relative to the current directory and with `/`
+/// separators.
+///
+/// The files outside the current directory keep their absolute path.
+#[must_use]
+pub fn relative_path(file: &str) -> String {
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    let path = Path::new(file);
+    let path = path.strip_prefix(&current_dir).unwrap_or(path);
+    let path = path.strip_prefix(".").unwrap_or(path);
+
+    let relative_path = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            Component::ParentDir => Some("..".into()),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    if path.has_root() {
+        format!("/{relative_path}")
+    } else {
+        relative_path
+    }
+}
 
 /// Reads the .rs files in the directory recursively.
 ///
@@ -26,46 +62,243 @@ pub fn fetch_docs(dir: &PathBuf) -> Result<Vec<(String, RawDocs)>> {
         .filter_map(|entry| Some(entry.path().to_str()?.to_owned()))
         .map(|path| {
             let content = std::fs::read_to_string(&path)?;
-            let stream: TokenStream = syn::parse_str(&content)?;
-            Ok((path, RawDocs::from(stream)))
+            let docs = match syn::parse_file(&content) {
+                Ok(file) => RawDocs::from(&file),
+                Err(error) => {
+                    // fall back to the tokens, without the item scopes.
+                    debug!("Failed to parse the items of {path}: {error}");
+                    let stream: TokenStream = syn::parse_str(&content)?;
+                    RawDocs::from(stream)
+                }
+            };
+            Ok((path, docs))
         })
         .filter(|result| result.as_ref().map(|(_, docs)| docs.is_empty()).ok() != Some(true))
         .collect::<Result<_>>()
 }
 
-/// Check the grammar of the documents and annotates the results.
+/// The results of checking the documents.
+#[derive(Debug, Default)]
+pub struct CheckResults {
+    pub findings: Vec<Finding>,
+    /// Number of checked doc blocks.
+    pub n_docs: usize,
+    /// Number of doc blocks whose check came from the cache.
+    pub n_cached_docs: usize,
+}
+
+/// Check the grammar of the documents and returns the findings.
 ///
 /// # Errors
 /// If an error occurs.
-pub fn check_and_annotate<I: IntoIterator<Item = (String, RawDocs)>, C: Cacheable>(
-    server: &languagetool_rust::ServerClient,
+pub fn check_files<I: IntoIterator<Item = (String, RawDocs)>, S: Checker, C: Cacheable>(
+    checker: &S,
     config: &Config,
     files: I,
     cache: &C,
-) -> Result<()> {
+) -> Result<CheckResults> {
+    let language_mismatch_severity = match config.language_mismatch.unwrap_or_default() {
+        LanguageMismatch::Off => None,
+        LanguageMismatch::Warn => Some(Severity::Warning),
+        LanguageMismatch::Error => Some(Severity::Error),
+    };
+
+    let files: Vec<_> = files.into_iter().collect();
+
+    // the names of the documented items, to recognize them in the prose.
+    let item_names = files
+        .iter()
+        .flat_map(|(_, docs)| &docs.0)
+        .flat_map(|doc| doc.item_path.split("::"))
+        .filter(|name| !name.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    let glossary = Glossary::load(config.glossary())?;
+    let rules = Rules::new(config, item_names, glossary);
+
+    let dictionary = Dictionary::load(config.dictionary())?;
+
+    let mut results = CheckResults::default();
+
     for (file, doc) in files {
         let mut docs = Docs::try_from(doc)?;
 
+        let file_language = config.file_language(&file);
+
         for doc in &mut docs.fixed {
-            doc.checked(server, config, cache)?;
+            if doc.language.is_none() {
+                doc.language = file_language.map(ToOwned::to_owned);
+            }
+            doc.checked(checker, config, cache)?;
+
+            results.n_docs += 1;
+            if doc.cached {
+                results.n_cached_docs += 1;
+            }
         }
 
         let source = std::fs::read_to_string(&file)?;
 
+        let suppressions = Suppressions::from(source.as_str());
+
         for doc in &mut docs.fixed {
+            doc.check_rules(&rules)?;
             doc.transform_matches(&source);
-            doc.annotate(&file, &source);
+            doc.suppress(&source, &suppressions);
+            results
+                .findings
+                .extend(doc.findings(&file, &source, config));
+
+            let is_language_mismatch_suppressed = doc.is_allowed(LANGUAGE_MISMATCH_ID, "")
+                || doc.text.first().is_some_and(|(_, span)| {
+                    suppressions.is_suppressed(span.start.line, LANGUAGE_MISMATCH_ID, "")
+                });
+
+            if let Some(finding) = language_mismatch_severity
+                .filter(|_| !is_language_mismatch_suppressed)
+                .and_then(|severity| {
+                    doc.language_mismatch_finding(&file, &source, doc.language(config), severity)
+                })
+            {
+                results.findings.push(finding);
+            }
         }
     }
 
-    Ok(())
+    results
+        .findings
+        .retain(|finding| !dictionary.allows(&finding.category_id, &finding.matched_text));
+
+    Ok(results)
 }
 
-// fn fix_string(s: &str) -> String {
-//     s.replace("/// ", "")
-//         .replace("//! ", "")
-//         .replace(r#"\""#, r#"""#)
-//         .trim_matches('\"')
-//         .trim()
-//         .to_owned()
-// }
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use clap::Parser;
+    use languagetool_rust::CheckResponse;
+
+    use super::*;
+    use crate::cache::sled::SledCacheStore;
+    use crate::checker::Request;
+    use crate::gate::failures;
+    use crate::languagetool::local::{local_response, LocalMatch};
+
+    const SOURCE: &str = "/// Teh value.\nfn value() {}\n";
+
+    /// Reports the misspelling `Teh`, and counts the checks.
+    #[derive(Default)]
+    struct Typos(Cell<usize>);
+
+    impl Checker for Typos {
+        fn check(&self, request: &Request) -> Result<CheckResponse> {
+            self.0.set(self.0.get() + 1);
+
+            let text = request.check_request.text.clone().unwrap_or_default();
+            let matches = text
+                .find("Teh")
+                .map(|offset| {
+                    LocalMatch {
+                        offset: text[..offset].chars().count(),
+                        length: 3,
+                        message: "Possible spelling mistake found.".to_owned(),
+                        short_message: "Spelling mistake".to_owned(),
+                        replacements: vec!["The".to_owned()],
+                        rule_id: "MORFOLOGIK_RULE_EN_US".to_owned(),
+                        rule_description: "Possible spelling mistake".to_owned(),
+                        issue_type: "misspelling".to_owned(),
+                        category_id: "TYPOS".to_owned(),
+                        category_name: "Possible Typo".to_owned(),
+                    }
+                    .into_match(&text)
+                })
+                .transpose()?;
+
+            local_response("en-US", &matches.into_iter().collect::<Vec<_>>())
+        }
+    }
+
+    #[test]
+    fn gate_on_cached_docs() {
+        let dir = std::env::temp_dir().join(format!("languagetool-gate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("lib.rs");
+        std::fs::write(&file, SOURCE).unwrap();
+        let file = file.to_string_lossy().into_owned();
+
+        let cache = SledCacheStore::new(dir.join("cache")).unwrap();
+        let checker = Typos::default();
+        let mut config = Config::parse_from(["languagetool", "--deny", "TYPOS"]);
+        config.show_all = config.needs_cached_findings();
+
+        let run = || {
+            let docs = RawDocs::from(&syn::parse_file(SOURCE).unwrap());
+            let results = check_files(&checker, &config, [(file.clone(), docs)], &cache).unwrap();
+            (results.n_cached_docs, failures(&config, &results.findings))
+        };
+
+        let first = run();
+        let second = run();
+
+        assert_eq!(checker.0.get(), 1);
+        assert_eq!((first.0, second.0), (0, 1));
+        assert_eq!(first.1, vec!["1 findings are denied".to_owned()]);
+        assert_eq!(second.1, first.1);
+
+        drop(cache);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Detects the docs as German.
+    struct German;
+
+    impl Checker for German {
+        fn check(&self, _request: &Request) -> Result<CheckResponse> {
+            let mut response = local_response("en-US", &[])?;
+            response.language.detected_language.code = "de-DE".to_owned();
+            response.language.detected_language.name = "German (Germany)".to_owned();
+            response.language.detected_language.confidence = Some(0.95);
+            Ok(response)
+        }
+
+        fn is_cacheable(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn language_mismatch_severity() {
+        let source = "impl Size {\n    /// Größe der Straße.\n    fn size() {}\n}\n";
+        let dir =
+            std::env::temp_dir().join(format!("languagetool-mismatch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("lib.rs");
+        std::fs::write(&file, source).unwrap();
+        let file = file.to_string_lossy().into_owned();
+        let cache = SledCacheStore::new(dir.join("cache")).unwrap();
+
+        let findings = |language_mismatch: &str| {
+            let config =
+                Config::parse_from(["languagetool", "--language-mismatch", language_mismatch]);
+            let docs = RawDocs::from(&syn::parse_file(source).unwrap());
+            check_files(&German, &config, [(file.clone(), docs)], &cache)
+                .unwrap()
+                .findings
+        };
+
+        assert!(findings("off").is_empty());
+        for (language_mismatch, severity) in
+            [("warn", Severity::Warning), ("error", Severity::Error)]
+        {
+            let findings = findings(language_mismatch);
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].rule_id, LANGUAGE_MISMATCH_ID);
+            assert_eq!(findings[0].severity, severity);
+            assert_eq!(&source[findings[0].byte_range.clone()], "Größe der Straße.");
+        }
+
+        drop(cache);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,392 @@
+//! The `visitor` module collects the doc comments by walking the item tree of a file.
+//!
+//! Walking the items keeps track of the scope of each doc comment: its item path, its language
+//! and the rules allowed in it, with `#[cfg_attr(any(), languagetool::allow(TYPOS))]` or a
+//! hidden `<!-- languagetool::allow(TYPOS) -->` line in the docs.
+
+use proc_macro2::{TokenStream, TokenTree};
+use syn::visit::{self, Visit};
+use syn::{
+    AttrStyle, Attribute, Expr, ExprLit, Field, File, ForeignItem, ImplItem, Item, Lit, LitStr,
+    Macro, Meta, TraitItem, Type, Variant,
+};
+
+use crate::doc::{language_directive, RawDoc, RawDocs};
+
+/// Returns the rules allowed by a hidden doc line, e.g. `<!-- languagetool::allow(TYPOS) -->`.
+fn allow_directive(text: &str) -> Option<Vec<String>> {
+    let text = text
+        .trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim();
+    let ids = text
+        .strip_prefix("languagetool::allow(")?
+        .strip_suffix(')')?;
+
+    Some(
+        ids.split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(ToOwned::to_owned)
+            .collect(),
+    )
+}
+
+/// Returns the rules allowed in the tokens of an attribute, e.g. `any(), languagetool::allow(TYPOS)`.
+fn allow_attribute(tokens: TokenStream) -> Vec<String> {
+    let trees: Vec<_> = tokens.into_iter().collect();
+
+    trees
+        .windows(5)
+        .filter_map(|window| match window {
+            [TokenTree::Ident(tool), TokenTree::Punct(first), TokenTree::Punct(second), TokenTree::Ident(allow), TokenTree::Group(group)]
+                if tool == "languagetool"
+                    && first.as_char() == ':'
+                    && second.as_char() == ':'
+                    && allow == "allow" =>
+            {
+                Some(group.stream())
+            }
+            _ => None,
+        })
+        .flat_map(|ids| {
+            ids.into_iter().filter_map(|tree| match tree {
+                TokenTree::Ident(id) => Some(id.to_string()),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+/// Returns the string of a `#[doc = "..."]` attribute.
+fn doc_string(attribute: &Attribute) -> Option<&LitStr> {
+    match &attribute.meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident("doc") => match &name_value.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(string),
+                ..
+            }) => Some(string),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    path: Vec<String>,
+    /// Whether the item is a function.
+    is_fn: bool,
+    language: Option<String>,
+    allowed: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct DocVisitor {
+    scope: Scope,
+    docs: Vec<RawDoc>,
+}
+
+impl DocVisitor {
+    /// Collects the docs of an item and visits its content in its scope.
+    fn scoped(
+        &mut self,
+        name: Option<String>,
+        is_fn: bool,
+        attributes: &[Attribute],
+        visit: impl FnOnce(&mut Self),
+    ) {
+        let parent = self.scope.clone();
+
+        self.scope.path.extend(name);
+        self.scope.is_fn = is_fn;
+
+        for attribute in attributes {
+            if let Some(string) = doc_string(attribute) {
+                let text = string.value();
+                if let Some(language) = language_directive(&text)
+                    .filter(|_| matches!(attribute.style, AttrStyle::Inner(_)))
+                {
+                    self.scope.language = Some(language);
+                } else if let Some(ids) = allow_directive(&text) {
+                    self.scope.allowed.extend(ids);
+                }
+            } else if let Meta::List(list) = &attribute.meta {
+                self.scope
+                    .allowed
+                    .extend(allow_attribute(list.tokens.clone()));
+            }
+        }
+
+        for attribute in attributes {
+            if let Some(string) = doc_string(attribute) {
+                let text = string.value();
+                if language_directive(&text).is_none() && allow_directive(&text).is_none() {
+                    self.push(RawDoc::from(string.token()));
+                }
+            } else if let Meta::List(list) = &attribute.meta {
+                // e.g. `#[cfg_attr(feature = "foo", doc = "...")]`
+                if list.path.is_ident("cfg_attr") {
+                    self.extend(RawDocs::from(list.tokens.clone()));
+                }
+            }
+        }
+
+        visit(self);
+
+        self.scope = parent;
+    }
+
+    /// Adds a doc in the current scope.
+    fn push(&mut self, mut doc: RawDoc) {
+        if doc.language.is_none() {
+            doc.language.clone_from(&self.scope.language);
+        }
+        doc.item_path = self.scope.path.join("::");
+        doc.is_fn = self.scope.is_fn;
+        doc.allowed.clone_from(&self.scope.allowed);
+        self.docs.push(doc);
+    }
+
+    /// Adds docs in the current scope.
+    fn extend(&mut self, docs: RawDocs) {
+        for doc in docs.0 {
+            self.push(doc);
+        }
+    }
+}
+
+/// Returns the name of an implemented type, e.g. `Foo` for `impl<T> Bar for Foo<T>`.
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+impl<'ast> Visit<'ast> for DocVisitor {
+    fn visit_file(&mut self, file: &'ast File) {
+        self.scoped(None, false, &file.attrs, |this| {
+            visit::visit_file(this, file);
+        });
+    }
+
+    fn visit_item(&mut self, item: &'ast Item) {
+        let (name, attributes) = match item {
+            Item::Const(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Enum(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::ExternCrate(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Fn(item) => (Some(item.sig.ident.to_string()), item.attrs.as_slice()),
+            Item::ForeignMod(item) => (None, item.attrs.as_slice()),
+            Item::Impl(item) => (type_name(&item.self_ty), item.attrs.as_slice()),
+            Item::Macro(item) => (
+                item.ident.as_ref().map(ToString::to_string),
+                item.attrs.as_slice(),
+            ),
+            Item::Mod(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Static(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Struct(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Trait(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::TraitAlias(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Type(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Union(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            Item::Use(item) => (None, item.attrs.as_slice()),
+            _ => (None, [].as_slice()),
+        };
+
+        let is_fn = matches!(item, Item::Fn(_));
+        self.scoped(name, is_fn, attributes, |this| {
+            visit::visit_item(this, item);
+        });
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast ImplItem) {
+        let (name, attributes) = match item {
+            ImplItem::Const(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            ImplItem::Fn(item) => (Some(item.sig.ident.to_string()), item.attrs.as_slice()),
+            ImplItem::Type(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            ImplItem::Macro(item) => (None, item.attrs.as_slice()),
+            _ => (None, [].as_slice()),
+        };
+
+        let is_fn = matches!(item, ImplItem::Fn(_));
+        self.scoped(name, is_fn, attributes, |this| {
+            visit::visit_impl_item(this, item);
+        });
+    }
+
+    fn visit_trait_item(&mut self, item: &'ast TraitItem) {
+        let (name, attributes) = match item {
+            TraitItem::Const(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            TraitItem::Fn(item) => (Some(item.sig.ident.to_string()), item.attrs.as_slice()),
+            TraitItem::Type(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            TraitItem::Macro(item) => (None, item.attrs.as_slice()),
+            _ => (None, [].as_slice()),
+        };
+
+        let is_fn = matches!(item, TraitItem::Fn(_));
+        self.scoped(name, is_fn, attributes, |this| {
+            visit::visit_trait_item(this, item);
+        });
+    }
+
+    fn visit_foreign_item(&mut self, item: &'ast ForeignItem) {
+        let (name, attributes) = match item {
+            ForeignItem::Fn(item) => (Some(item.sig.ident.to_string()), item.attrs.as_slice()),
+            ForeignItem::Static(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            ForeignItem::Type(item) => (Some(item.ident.to_string()), item.attrs.as_slice()),
+            ForeignItem::Macro(item) => (None, item.attrs.as_slice()),
+            _ => (None, [].as_slice()),
+        };
+
+        let is_fn = matches!(item, ForeignItem::Fn(_));
+        self.scoped(name, is_fn, attributes, |this| {
+            visit::visit_foreign_item(this, item);
+        });
+    }
+
+    fn visit_field(&mut self, field: &'ast Field) {
+        let name = field.ident.as_ref().map(ToString::to_string);
+        self.scoped(name, false, &field.attrs, |this| {
+            visit::visit_field(this, field);
+        });
+    }
+
+    fn visit_variant(&mut self, variant: &'ast Variant) {
+        let name = Some(variant.ident.to_string());
+        self.scoped(name, false, &variant.attrs, |this| {
+            visit::visit_variant(this, variant);
+        });
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        // the macro body is not parsed, e.g. the docs of the items generated by `macro_rules!`.
+        self.extend(RawDocs::from(mac.tokens.clone()));
+    }
+}
+
+impl From<&File> for RawDocs {
+    fn from(file: &File) -> Self {
+        let mut visitor = DocVisitor::default();
+        visitor.visit_file(file);
+        Self(visitor.docs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::doc::tests::docs;
+    use crate::doc::Docs;
+    use crate::utils::fetch_docs;
+
+    /// Returns the item path, whether it is a function, and the allowed rules of the docs.
+    fn scopes(source: &str) -> Vec<(String, bool, Vec<String>)> {
+        docs(source)
+            .into_iter()
+            .map(|doc| (doc.item_path, doc.is_fn, doc.allowed))
+            .collect()
+    }
+
+    #[test]
+    fn allow_attribute_in_nested_items() {
+        let source = r"
+#[cfg_attr(any(), languagetool::allow(TYPOS, EN_A_VS_AN))]
+mod foo {
+    /// Docs of `Bar`.
+    struct Bar {
+        /// Docs of `baz`.
+        baz: u8,
+    }
+}
+
+/// Docs of `qux`.
+fn qux() {}
+";
+        let allowed = vec!["TYPOS".to_owned(), "EN_A_VS_AN".to_owned()];
+
+        assert_eq!(
+            scopes(source),
+            vec![
+                ("foo::Bar".to_owned(), false, allowed.clone()),
+                ("foo::Bar::baz".to_owned(), false, allowed),
+                ("qux".to_owned(), true, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_allow_line() {
+        let source = r"
+/// <!-- languagetool::allow(TYPOS) -->
+/// Teh value.
+/// Another line.
+fn value() {}
+";
+        let docs = docs(source);
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].allowed, vec!["TYPOS".to_owned()]);
+        assert_eq!(docs[0].to_string(), "Teh value.\nAnother line.");
+    }
+
+    #[test]
+    fn method_paths() {
+        let source = r"
+struct Foo<T>(T);
+
+impl<T> Foo<T> {
+    /// Docs of `Foo::bar`.
+    fn bar() {}
+}
+
+trait Baz {
+    /// Docs of `Baz::qux`.
+    fn qux();
+    /// Docs of `Baz::Item`.
+    type Item;
+}
+
+impl<T> Baz for Foo<T> {
+    /// Docs of `<Foo as Baz>::qux`.
+    fn qux() {}
+}
+";
+
+        assert_eq!(
+            scopes(source),
+            vec![
+                ("Foo::bar".to_owned(), true, vec![]),
+                ("Baz::qux".to_owned(), true, vec![]),
+                ("Baz::Item".to_owned(), false, vec![]),
+                ("Foo::qux".to_owned(), true, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fall_back_to_the_tokens() {
+        // the items do not parse, but the tokens do.
+        let source = "/// Docs of `foo`.\nfoo bar!;\n";
+        assert!(syn::parse_file(source).is_err());
+
+        let dir = std::env::temp_dir().join(format!("languagetool-visitor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("lib.rs"), source).unwrap();
+
+        let files = fetch_docs(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 1);
+        let docs = Docs::try_from(files.into_iter().next().unwrap().1)
+            .unwrap()
+            .fixed;
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].to_string(), "Docs of `foo`.");
+        assert_eq!(docs[0].item_path, "");
+    }
+}